@@ -1,20 +1,33 @@
 use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose};
-use std::io::{Cursor, BufRead, BufReader};
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}, mpsc};
+use std::io::{BufRead, BufReader};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicUsize, Ordering}, mpsc};
 use windows::Win32::Foundation::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::traits::{DeviceTrait, StreamTrait};
 use crate::config::Preset;
 use crate::model_config::get_model_by_id;
 use crate::APP;
+use crate::audio::vad::{Vad, VadState, trim_silence};
+use crate::audio::resample::{to_speech_format, TARGET_SAMPLE_RATE};
+use crate::audio::encode::{encode, AudioFormat};
+use crate::audio::chunk::{split_at_silence, stitch_transcripts};
+use crate::audio::decode::decode_audio_file;
+use crate::audio::device::{capture_host, input_device, output_device};
+use crate::audio::mix::CaptureMixer;
+use crate::audio::error::AudioError;
+use super::live::LiveTranscriber;
 use super::client::UREQ_AGENT;
+use super::types::{Transcript, TranscriptSegment, diarized_text, speakers_of};
+use crate::overlay::result::RefineContext;
 
 pub fn transcribe_audio_gemini<F>(
     gemini_api_key: &str,
     prompt: String,
     model: String,
-    wav_data: Vec<u8>,
+    audio_data: Vec<u8>,
     mut on_chunk: F,
 ) -> Result<String>
 where
@@ -24,7 +37,8 @@ where
         return Err(anyhow::anyhow!("NO_API_KEY"));
     }
 
-    let b64_audio = general_purpose::STANDARD.encode(&wav_data);
+    let mime_type = AudioFormat::detect(&audio_data).mime_type();
+    let b64_audio = general_purpose::STANDARD.encode(&audio_data);
     let url = format!(
         "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse",
         model
@@ -37,7 +51,7 @@ where
                 { "text": prompt },
                 {
                     "inline_data": {
-                        "mime_type": "audio/wav",
+                        "mime_type": mime_type,
                         "data": b64_audio
                     }
                 }
@@ -90,7 +104,7 @@ where
     Ok(full_content)
 }
 
-fn upload_audio_to_whisper(api_key: &str, model: &str, audio_data: Vec<u8>) -> anyhow::Result<String> {
+fn upload_audio_to_whisper(api_key: &str, model: &str, audio_data: Vec<u8>, preset: &Preset) -> anyhow::Result<Transcript> {
     // Create multipart form data
     let boundary = format!("----SGTBoundary{}", std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -104,11 +118,32 @@ fn upload_audio_to_whisper(api_key: &str, model: &str, audio_data: Vec<u8>) -> a
     body.extend_from_slice(b"Content-Disposition: form-data; name=\"model\"\r\n\r\n");
     body.extend_from_slice(model.as_bytes());
     body.extend_from_slice(b"\r\n");
+
+    // Optional Whisper settings from the preset
+    let mut add_field = |name: &str, value: &str| {
+        body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+        body.extend_from_slice(format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name).as_bytes());
+        body.extend_from_slice(value.as_bytes());
+        body.extend_from_slice(b"\r\n");
+    };
+    if !preset.whisper_language.trim().is_empty() {
+        add_field("language", preset.whisper_language.trim());
+    }
+    if !preset.whisper_prompt.trim().is_empty() {
+        add_field("prompt", preset.whisper_prompt.trim());
+    }
+    add_field("temperature", &format!("{}", preset.whisper_temperature));
+    if preset.timestamps {
+        add_field("response_format", "verbose_json");
+        add_field("timestamp_granularities[]", "segment");
+    }
     
     // Add file field
     body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-    body.extend_from_slice(b"Content-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\n");
-    body.extend_from_slice(b"Content-Type: audio/wav\r\n\r\n");
+    // Whisper infers the codec from the file extension
+    let format = AudioFormat::detect(&audio_data);
+    body.extend_from_slice(format!("Content-Disposition: form-data; name=\"file\"; filename=\"audio.{}\"\r\n", format.extension()).as_bytes());
+    body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", format.mime_type()).as_bytes());
     body.extend_from_slice(&audio_data);
     body.extend_from_slice(b"\r\n");
     
@@ -139,71 +174,227 @@ fn upload_audio_to_whisper(api_key: &str, model: &str, audio_data: Vec<u8>) -> a
     let text = json.get("text")
         .and_then(|t| t.as_str())
         .ok_or_else(|| anyhow::anyhow!("No text in response"))?;
+
+    // verbose_json only
+    let segments = json.get("segments").and_then(|s| s.as_array()).map(|segments| {
+        segments.iter().filter_map(|seg| Some(TranscriptSegment {
+            start: seg.get("start")?.as_f64()?,
+            end: seg.get("end")?.as_f64()?,
+            text: seg.get("text")?.as_str()?.trim().to_string(),
+            speaker: String::new(),
+        })).collect()
+    }).unwrap_or_default();
     
-    Ok(text.to_string())
+    Ok(Transcript { text: text.to_string(), segments })
 }
 
-pub fn record_audio_and_transcribe(
-    preset: Preset, 
-    stop_signal: Arc<AtomicBool>, 
-    pause_signal: Arc<AtomicBool>,
-    abort_signal: Arc<AtomicBool>,
-    overlay_hwnd: HWND
-) {
-    #[cfg(target_os = "windows")]
-    let host = if preset.audio_source == "device" {
-        cpal::host_from_id(cpal::HostId::Wasapi).unwrap_or(cpal::default_host())
-    } else {
-        cpal::default_host()
+// Gemini has no segment API, so timestamps are requested in the text and parsed back
+const GEMINI_TIMESTAMP_INSTRUCTION: &str = "\n\nStart every sentence on a new line prefixed with its start time in the audio as [mm:ss]. Output nothing else.";
+// Speaker-labelled transcript for conversations, parsed by Transcript::from_diarized_json
+const GEMINI_DIARIZATION_INSTRUCTION: &str = "\n\nThere may be several speakers. Output ONLY a JSON array, one object per utterance in order: {\"speaker\": \"Speaker 1\", \"start\": seconds, \"end\": seconds, \"text\": \"...\"}. Keep the same label for the same voice throughout.";
+
+/// Sends an encoded recording (WAV/FLAC) to the preset's audio model and returns the transcript
+pub fn transcribe_audio_data(preset: &Preset, audio_data: Vec<u8>) -> Result<Transcript> {
+    let model_config = get_model_by_id(&preset.model)
+        .ok_or_else(|| anyhow::anyhow!("Model config not found for preset model"))?;
+    let model_name = model_config.full_name.clone();
+    let provider = model_config.provider.clone();
+    
+    let (groq_api_key, gemini_api_key) = {
+        let app = crate::APP.lock().unwrap();
+        (app.config.api_key.clone(), app.config.gemini_api_key.clone())
     };
-    #[cfg(not(target_os = "windows"))]
-    let host = cpal::default_host();
-
-    let device = if preset.audio_source == "device" {
-        #[cfg(target_os = "windows")]
-        {
-            match host.default_output_device() {
-                Some(d) => d,
-                None => {
-                    eprintln!("Error: No default output device found for loopback.");
-                    host.default_input_device().expect("No input device available")
-                }
-            }
+
+    let mut final_prompt = preset.expanded_prompt();
+    
+    if provider == "groq" {
+        if groq_api_key.trim().is_empty() {
+            Err(anyhow::anyhow!("NO_API_KEY"))
+        } else {
+            upload_audio_to_whisper(&groq_api_key, &model_name, audio_data, preset)
         }
-        #[cfg(not(target_os = "windows"))]
-        {
-            host.default_input_device().expect("No input device available")
+    } else if provider == "google" {
+        if gemini_api_key.trim().is_empty() {
+            Err(anyhow::anyhow!("NO_API_KEY"))
+        } else {
+            if preset.diarization {
+                final_prompt.push_str(GEMINI_DIARIZATION_INSTRUCTION);
+                transcribe_audio_gemini(&gemini_api_key, final_prompt, model_name, audio_data, |_| {}).map(Transcript::from_diarized_json)
+            } else if preset.timestamps {
+                final_prompt.push_str(GEMINI_TIMESTAMP_INSTRUCTION);
+                transcribe_audio_gemini(&gemini_api_key, final_prompt, model_name, audio_data, |_| {}).map(Transcript::from_timestamped)
+            } else {
+                transcribe_audio_gemini(&gemini_api_key, final_prompt, model_name, audio_data, |_| {}).map(Transcript::plain)
+            }
         }
     } else {
-        host.default_input_device().expect("No input device available")
-    };
+        Err(anyhow::anyhow!("Unsupported audio provider: {}", provider))
+    }
+}
 
-    let config = if preset.audio_source == "device" {
-        match device.default_output_config() {
-            Ok(c) => c,
-            Err(_) => {
-                 device.default_input_config().expect("Failed to get audio config")
+/// Centered result window placement for audio results (side by side when retranslating)
+pub fn audio_result_rects(retranslate: bool) -> (RECT, Option<RECT>) {
+    let screen_w = unsafe { GetSystemMetrics(SM_CXSCREEN) };
+    let screen_h = unsafe { GetSystemMetrics(SM_CYSCREEN) };
+    
+    if retranslate {
+        let w = 600;
+        let h = 300;
+        let gap = 20;
+        let total_w = w * 2 + gap;
+        let start_x = (screen_w - total_w) / 2;
+        let y = (screen_h - h) / 2;
+        
+        (
+            RECT { left: start_x, top: y, right: start_x + w, bottom: y + h },
+            Some(RECT { left: start_x + w + gap, top: y, right: start_x + w + gap + w, bottom: y + h })
+        )
+    } else {
+        let w = 700;
+        let h = 300;
+        let x = (screen_w - w) / 2;
+        let y = (screen_h - h) / 2;
+        (RECT { left: x, top: y, right: x + w, bottom: y + h }, None)
+    }
+}
+
+// --- LONG RECORDINGS ---
+const CHUNK_OVERLAP_SECS: u32 = 2;
+const MAX_PARALLEL_CHUNKS: usize = 3;
+
+// Chunk length that keeps one upload under the provider's request size limit
+// (Groq Whisper: 25 MB file, Gemini: 20 MB inline request incl. base64 overhead)
+fn max_chunk_secs(preset: &Preset) -> u32 {
+    let provider = get_model_by_id(&preset.model).map(|m| m.provider).unwrap_or_default();
+    match provider.as_str() {
+        "google" => 300,
+        _ => 600,
+    }
+}
+
+// Diarized chunks are labelled separately, so each chunk after the first is told
+// which labels were used so far and a line each voice said
+fn speaker_context_note(segments: &[TranscriptSegment]) -> String {
+    let speakers = speakers_of(segments);
+    if speakers.is_empty() {
+        return String::new();
+    }
+    let samples: Vec<String> = speakers.iter().filter_map(|speaker| {
+        let line = segments.iter().rev().find(|s| &s.speaker == speaker)?;
+        let quote: String = line.text.chars().take(100).collect();
+        Some(format!("{}: \"{}\"", speaker, quote))
+    }).collect();
+    format!(
+        "\n\nThis audio continues an earlier part of the same recording. Speakers identified so far, with something each said: {}. Use the same label for the same voice; give a new voice the next unused number.",
+        samples.join("; ")
+    )
+}
+
+// Transcribes chunks with bounded parallelism. `on_partial` receives the stitched
+// text of every chunk finished so far, in order. Segment times are shifted to
+// the position of their chunk in the whole recording. Diarized (Gemini) chunks run
+// one after another so each can be given the speaker labels of the ones before.
+fn transcribe_in_chunks<F>(
+    preset: &Preset,
+    samples: &[f32],
+    chunks: &[Range<usize>],
+    format: AudioFormat,
+    abort_signal: &AtomicBool,
+    mut on_partial: F,
+) -> Result<Transcript>
+where
+    F: FnMut(&str),
+{
+    let results: Mutex<Vec<Option<Result<Transcript>>>> = Mutex::new((0..chunks.len()).map(|_| None).collect());
+    let next_chunk = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<()>();
+    let provider = get_model_by_id(&preset.model).map(|m| m.provider).unwrap_or_default();
+    let diarize = preset.diarization && provider == "google";
+    let parallel = if diarize { 1 } else { MAX_PARALLEL_CHUNKS };
+
+    std::thread::scope(|scope| {
+        for _ in 0..parallel.min(chunks.len()) {
+            let tx = tx.clone();
+            let (results, next_chunk) = (&results, &next_chunk);
+            scope.spawn(move || loop {
+                let i = next_chunk.fetch_add(1, Ordering::SeqCst);
+                if i >= chunks.len() || abort_signal.load(Ordering::SeqCst) { break; }
+
+                let chunk_preset = if diarize && i > 0 {
+                    // Chunks run in order here, so every earlier result is in
+                    let earlier: Vec<TranscriptSegment> = results.lock().unwrap()[..i].iter()
+                        .filter_map(|r| match r { Some(Ok(t)) => Some(t.segments.clone()), _ => None })
+                        .flatten()
+                        .collect();
+                    let mut p = preset.clone();
+                    p.prompt.push_str(&speaker_context_note(&earlier));
+                    p
+                } else {
+                    preset.clone()
+                };
+
+                let res = encode(&samples[chunks[i].clone()], TARGET_SAMPLE_RATE, 1, format)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|data| transcribe_audio_data(&chunk_preset, data));
+                if res.is_err() {
+                    // No point transcribing the rest once one piece is missing
+                    next_chunk.store(chunks.len(), Ordering::SeqCst);
+                }
+                results.lock().unwrap()[i] = Some(res);
+                let _ = tx.send(());
+            });
+        }
+        drop(tx);
+
+        let mut shown = 0;
+        while rx.recv().is_ok() {
+            let done: Vec<String> = results.lock().unwrap().iter()
+                .map_while(|r| match r { Some(Ok(t)) => Some(t.text.clone()), _ => None })
+                .collect();
+            if done.len() > shown {
+                shown = done.len();
+                on_partial(&stitch_transcripts(&done));
             }
         }
-    } else {
-        device.default_input_config().expect("Failed to get audio config")
-    };
+    });
 
-    let sample_rate = config.sample_rate().0;
-    let channels = config.channels();
-    
-    let spec = hound::WavSpec {
-        channels,
-        sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
+    let mut texts = Vec::new();
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    for (res, range) in results.into_inner().unwrap().into_iter().zip(chunks) {
+        match res {
+            Some(Ok(t)) => {
+                let offset = range.start as f64 / TARGET_SAMPLE_RATE as f64;
+                let covered_until = segments.last().map_or(0.0, |s| s.end);
+                // Segments from the overlap were already transcribed by the previous chunk
+                segments.extend(t.segments.into_iter()
+                    .map(|s| TranscriptSegment { start: s.start + offset, end: s.end + offset, ..s })
+                    .filter(|s| s.end > covered_until + 0.5));
+                texts.push(t.text);
+            },
+            Some(Err(e)) => return Err(e),
+            None => return Err(anyhow::anyhow!("Transcription was interrupted")),
+        }
+    }
+    // Word-level overlap removal would merge "Speaker N:" lines; diarized segments
+    // were already de-duplicated by time above
+    let text = if segments.iter().any(|s| !s.speaker.is_empty()) {
+        diarized_text(&segments)
+    } else {
+        stitch_transcripts(&texts)
     };
+    Ok(Transcript { text, segments })
+}
 
-    let (tx, rx) = mpsc::channel::<Vec<f32>>();
-
+// Input stream delivering f32 chunks to `tx`. On an output device (WASAPI) this captures loopback.
+fn build_capture_stream(
+    device: &cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    tx: mpsc::Sender<Vec<f32>>,
+    pause_signal: Arc<AtomicBool>,
+) -> Result<cpal::Stream, AudioError> {
     let err_fn = |err| eprintln!("Audio stream error: {}", err);
     
-    let stream_res = match config.sample_format() {
+    let stream = match config.sample_format() {
         cpal::SampleFormat::F32 => device.build_input_stream(
             &config.into(),
             move |data: &[f32], _: &_| {
@@ -233,31 +424,201 @@ pub fn record_audio_and_transcribe(
             err_fn,
             None
         ),
-        _ => {
-            eprintln!("Unsupported audio sample format: {:?}", config.sample_format());
-             Err(cpal::BuildStreamError::StreamConfigNotSupported)
+        other => {
+            return Err(AudioError::UnsupportedSampleFormat(format!("{:?}", other)));
+        },
+    }.map_err(|e| AudioError::Stream(e.to_string()))?;
+
+    stream.play().map_err(|e| AudioError::Stream(e.to_string()))?;
+    Ok(stream)
+}
+
+// Collects what the capture callbacks have sent. With a mixer, mic and system audio
+// come back mixed to mono; `flush` also returns the unmatched tail.
+fn drain_captured(rx: &mpsc::Receiver<Vec<f32>>, rx_system: &mpsc::Receiver<Vec<f32>>, mixer: Option<&mut CaptureMixer>, flush: bool) -> Vec<f32> {
+    match mixer {
+        Some(mixer) => {
+            while let Ok(chunk) = rx.try_recv() {
+                mixer.push_mic(&chunk);
+            }
+            while let Ok(chunk) = rx_system.try_recv() {
+                mixer.push_system(&chunk);
+            }
+            if flush { mixer.flush() } else { mixer.drain() }
         },
+        None => {
+            let mut samples = Vec::new();
+            while let Ok(chunk) = rx.try_recv() {
+                samples.extend(chunk);
+            }
+            samples
+        }
+    }
+}
+
+// Running capture: the main device plus, in mix mode, the system audio loopback
+struct Capture {
+    stream: cpal::Stream,
+    system_stream: Option<cpal::Stream>,
+    rx: mpsc::Receiver<Vec<f32>>,
+    rx_system: mpsc::Receiver<Vec<f32>>,
+    mixer: Option<CaptureMixer>,
+    sample_rate: u32,
+    channels: u16, // 1 when mixing
+}
+
+// Opens the preset's devices: "mic", "device" (loopback of an output device) or "mix" (mic + loopback)
+fn open_capture(preset: &Preset, pause_signal: Arc<AtomicBool>) -> Result<Capture, AudioError> {
+    let loopback = preset.audio_source == "device";
+    let mix = preset.audio_source == "mix";
+    let host = capture_host(loopback || mix);
+
+    let device = if loopback {
+        match output_device(&host, &preset.audio_output_device) {
+            Some(d) => d,
+            None => {
+                eprintln!("Error: No default output device found for loopback.");
+                input_device(&host, &preset.audio_input_device).ok_or(AudioError::NoOutputDevice)?
+            }
+        }
+    } else {
+        input_device(&host, &preset.audio_input_device).ok_or(AudioError::NoInputDevice)?
     };
 
-    if let Err(e) = stream_res {
-        eprintln!("Failed to build stream: {}", e);
-        unsafe { PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
-        return;
+    let config = if loopback {
+        device.default_output_config().or_else(|_| device.default_input_config())
+    } else {
+        device.default_input_config()
+    }.map_err(|e| AudioError::Config(e.to_string()))?;
+
+    let sample_rate = config.sample_rate().0;
+    let mut channels = config.channels();
+
+    let (tx, rx) = mpsc::channel::<Vec<f32>>();
+    let stream = build_capture_stream(&device, config, tx, pause_signal.clone())?;
+
+    // System audio for mix mode. Without it the recording is mic only.
+    let (tx_system, rx_system) = mpsc::channel::<Vec<f32>>();
+    let mut system_stream = None;
+    let mut mixer = None;
+    if mix {
+        let system = output_device(&host, &preset.audio_output_device)
+            .and_then(|d| d.default_output_config().ok().map(|c| (d, c)));
+        match system {
+            Some((system_device, system_config)) => {
+                let system_format = (system_config.sample_rate().0, system_config.channels());
+                match build_capture_stream(&system_device, system_config, tx_system, pause_signal) {
+                    Ok(s) => {
+                        mixer = Some(CaptureMixer::new(sample_rate, (sample_rate, channels), system_format));
+                        system_stream = Some(s);
+                        channels = 1;
+                    },
+                    Err(e) => eprintln!("Failed to start system audio capture: {}", e),
+                }
+            },
+            None => eprintln!("Error: No output device found for system audio capture."),
+        }
     }
-    let stream = stream_res.unwrap();
 
-    if let Err(e) = stream.play() {
-        eprintln!("Failed to play stream: {}", e);
-        unsafe { PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
-        return;
+    Ok(Capture { stream, system_stream, rx, rx_system, mixer, sample_rate, channels })
+}
+
+// 16 kHz mono for the upload; history keeps the source format only if the preset asks for it.
+// Returns (speech samples, upload data, history data).
+fn encode_for_upload(preset: &Preset, samples: &[f32], sample_rate: u32, channels: u16) -> Result<(Vec<f32>, Vec<u8>, Vec<u8>), AudioError> {
+    let speech_samples = to_speech_format(samples, sample_rate, channels);
+    let format = AudioFormat::from_preset(&preset.audio_format);
+    let audio_data = encode(&speech_samples, TARGET_SAMPLE_RATE, 1, format)?;
+    let audio_data_for_history = if preset.keep_original_audio {
+        encode(samples, sample_rate, channels, format)?
+    } else {
+        audio_data.clone()
+    };
+    Ok((speech_samples, audio_data, audio_data_for_history))
+}
+
+// History files may hold device-rate audio (keep_original_audio), so re-runs
+// go through the same 16 kHz mono stage as a fresh recording.
+// Returns the speech samples and their upload encoding.
+pub fn load_speech_file(preset: &Preset, path: &std::path::Path) -> Result<(Vec<f32>, Vec<u8>), AudioError> {
+    let decoded = decode_audio_file(path)?;
+    let speech_samples = to_speech_format(&decoded.samples, decoded.sample_rate, decoded.channels);
+    let audio_data = encode(&speech_samples, TARGET_SAMPLE_RATE, 1, AudioFormat::from_preset(&preset.audio_format))?;
+    Ok((speech_samples, audio_data))
+}
+
+// Re-runs a history recording with the same chunked upload as a fresh one;
+// the result is saved as a re-run of `parent_id`
+pub fn rerun_speech(preset: Preset, speech_samples: &[f32], audio_data: Vec<u8>, parent_id: i64) {
+    let abort_signal = AtomicBool::new(false);
+    transcribe_speech(preset, speech_samples, audio_data, Vec::new(), &abort_signal, HWND(0), Some(parent_id));
+}
+
+// Shows the error in the recording overlay (briefly, before it closes) and in a result window
+fn report_audio_error(preset: &Preset, overlay_hwnd: HWND, error: &AudioError) {
+    eprintln!("Audio error: {}", error);
+    let ui_language = APP.lock().unwrap().config.ui_language.clone();
+    let message = crate::overlay::utils::get_error_message(error.code(), &ui_language);
+
+    unsafe {
+        if IsWindow(overlay_hwnd).as_bool() {
+            if !preset.hide_recording_ui {
+                crate::overlay::recording::show_recording_error(message.clone());
+                std::thread::sleep(std::time::Duration::from_millis(2500));
+            }
+            PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+        }
     }
 
+    let (rect, _) = audio_result_rects(false);
+    let hwnd = crate::overlay::process::open_audio_result_window(preset, RefineContext::None, rect);
+    crate::overlay::result::update_window_text(hwnd, &message);
+}
+
+pub fn record_audio_and_transcribe(
+    preset: Preset, 
+    stop_signal: Arc<AtomicBool>, 
+    pause_signal: Arc<AtomicBool>,
+    abort_signal: Arc<AtomicBool>,
+    overlay_hwnd: HWND
+) {
+    let capture = match open_capture(&preset, pause_signal) {
+        Ok(c) => c,
+        Err(e) => {
+            report_audio_error(&preset, overlay_hwnd, &e);
+            return;
+        }
+    };
+    let Capture { stream, system_stream, rx, rx_system, mut mixer, sample_rate, channels } = capture;
+
     let mut collected_samples: Vec<f32> = Vec::new();
+    let mut vad = Vad::new(sample_rate, channels);
+    let auto_stop_ms = (preset.auto_stop_silence_secs.max(0.0) * 1000.0) as u32;
+    let mut live = if preset.live_transcribe {
+        Some(LiveTranscriber::start(&preset, sample_rate, channels))
+    } else {
+        None
+    };
 
     while !stop_signal.load(Ordering::SeqCst) {
-        while let Ok(chunk) = rx.try_recv() {
+        let chunk = drain_captured(&rx, &rx_system, mixer.as_mut(), false);
+        if !chunk.is_empty() {
+            vad.process(&chunk);
+            if let Some(live) = live.as_mut() {
+                live.push(&chunk);
+            }
             collected_samples.extend(chunk);
         }
+
+        // Auto-stop once the speaker has finished
+        if auto_stop_ms > 0 {
+            crate::overlay::recording::update_vad_state(vad.state(), vad.silence_ms());
+            if vad.state() == VadState::Silence && vad.silence_ms() >= auto_stop_ms {
+                stop_signal.store(true, Ordering::SeqCst);
+                break;
+            }
+        }
+
         std::thread::sleep(std::time::Duration::from_millis(50));
         if !preset.hide_recording_ui {
              if !unsafe { IsWindow(overlay_hwnd).as_bool() } {
@@ -267,6 +628,7 @@ pub fn record_audio_and_transcribe(
     }
 
     drop(stream);
+    drop(system_stream);
 
     if abort_signal.load(Ordering::SeqCst) {
         println!("Audio recording aborted by user.");
@@ -275,18 +637,24 @@ pub fn record_audio_and_transcribe(
                  PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
             }
         }
+        if let Some(live) = live {
+            live.close_windows();
+        }
         return;
     }
 
-    while let Ok(chunk) = rx.try_recv() {
-        collected_samples.extend(chunk);
+    let chunk = drain_captured(&rx, &rx_system, mixer.as_mut(), true);
+    if let Some(live) = live.as_mut() {
+        live.push(&chunk);
     }
+    collected_samples.extend(chunk);
 
-    let samples: Vec<i16> = collected_samples.iter()
-        .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
-        .collect();
+    // Drop leading/trailing silence so less audio is uploaded
+    let trimmed_range = trim_silence(&collected_samples, sample_rate, channels, 300);
+    let lead = trimmed_range.start;
+    let trimmed = &collected_samples[trimmed_range];
     
-    if samples.is_empty() {
+    if trimmed.is_empty() {
         println!("Warning: Recorded audio buffer is empty.");
         unsafe {
             PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
@@ -294,52 +662,114 @@ pub fn record_audio_and_transcribe(
         return;
     }
 
-    let mut wav_cursor = Cursor::new(Vec::new());
-    {
-        let mut writer = hound::WavWriter::new(&mut wav_cursor, spec).expect("Failed to create memory writer");
-        for sample in &samples {
-            writer.write_sample(*sample).expect("Failed to write sample");
+    let (speech_samples, audio_data, audio_data_for_history) = match encode_for_upload(&preset, trimmed, sample_rate, channels) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            if let Some(live) = live {
+                live.close_windows();
+            }
+            report_audio_error(&preset, overlay_hwnd, &e);
+            return;
         }
-        writer.finalize().expect("Failed to finalize WAV");
-    }
-    let wav_data = wav_cursor.into_inner();
-    
-    let model_config = get_model_by_id(&preset.model);
-    let model_config = model_config.expect("Model config not found for preset model");
-    let model_name = model_config.full_name.clone();
-    let provider = model_config.provider.clone();
-    
-    let (groq_api_key, gemini_api_key) = {
-        let app = crate::APP.lock().unwrap();
-        (app.config.api_key.clone(), app.config.gemini_api_key.clone())
     };
-
-    let mut final_prompt = preset.prompt.clone();
-    
-    for (key, value) in &preset.language_vars {
-        let pattern = format!("{{{}}}", key);
-        final_prompt = final_prompt.replace(&pattern, value);
-    }
-    
-    final_prompt = final_prompt.replace("{language}", &preset.selected_language);
-    
-    // Clone wav_data for history saving
-    let wav_data_for_history = wav_data.clone();
     
-    let transcription_result = if provider == "groq" {
-        if groq_api_key.trim().is_empty() {
-            Err(anyhow::anyhow!("NO_API_KEY"))
-        } else {
-            upload_audio_to_whisper(&groq_api_key, &model_name, wav_data)
+    // Live mode already transcribed everything while recording, only the tail is left
+    if let Some(live) = live {
+        let primary_hwnd = live.primary_hwnd;
+        let mut transcript = live.finish();
+        unsafe {
+            if IsWindow(overlay_hwnd).as_bool() {
+                 PostMessageW(overlay_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+            }
         }
-    } else if provider == "google" {
-        if gemini_api_key.trim().is_empty() {
-            Err(anyhow::anyhow!("NO_API_KEY"))
-        } else {
-            transcribe_audio_gemini(&gemini_api_key, final_prompt, model_name, wav_data, |_| {})
+        if transcript.text.trim().is_empty() {
+            return;
+        }
+
+        // Live segment times count from the start of the capture, history keeps the trimmed recording
+        let lead_secs = (lead / channels.max(1) as usize) as f64 / sample_rate as f64;
+        for seg in &mut transcript.segments {
+            seg.start = (seg.start - lead_secs).max(0.0);
+            seg.end = (seg.end - lead_secs).max(0.0);
         }
+
+        let history_id = {
+            let app = crate::APP.lock().unwrap();
+            app.history.save_audio(audio_data_for_history, transcript.text.clone(), transcript.segments.clone())
+        };
+        crate::overlay::result::set_window_history_id(primary_hwnd, history_id);
+        crate::overlay::result::set_window_context(primary_hwnd, RefineContext::Audio(audio_data));
+
+        if preset.auto_copy {
+            crate::overlay::utils::copy_to_clipboard(&transcript.text, HWND(0));
+        }
+        return;
+    }
+
+    transcribe_speech(preset, &speech_samples, audio_data, audio_data_for_history, &abort_signal, overlay_hwnd, None);
+}
+
+// --- AUDIO FILE INPUT ---
+// Transcribes an existing recording with the same upload, chunking, history and
+// retranslate steps as a live recording.
+pub fn transcribe_audio_file(preset: Preset, path: PathBuf) {
+    let encoded = decode_audio_file(&path)
+        .and_then(|decoded| encode_for_upload(&preset, &decoded.samples, decoded.sample_rate, decoded.channels));
+    let (speech_samples, audio_data, audio_data_for_history) = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            eprintln!("Failed to read {}", path.display());
+            report_audio_error(&preset, HWND(0), &e);
+            return;
+        }
+    };
+
+    let abort_signal = AtomicBool::new(false);
+    transcribe_speech(preset, &speech_samples, audio_data, audio_data_for_history, &abort_signal, HWND(0), None);
+}
+
+// Uploads 16 kHz mono speech (in chunks when long), saves history and shows the result.
+// `overlay_hwnd` is the recording overlay to close once the upload is done (HWND(0) if none).
+// With `parent_id` the result is saved as a re-run of that entry and the cache is skipped.
+fn transcribe_speech(preset: Preset, speech_samples: &[f32], audio_data: Vec<u8>, audio_data_for_history: Vec<u8>, abort_signal: &AtomicBool, overlay_hwnd: HWND, parent_id: Option<i64>) {
+    let format = AudioFormat::from_preset(&preset.audio_format);
+
+    // Long recordings are transcribed in pieces, with partial text streamed into an early result window
+    let (rect, retranslate_rect) = audio_result_rects(preset.retranslate);
+    let chunks = split_at_silence(speech_samples, TARGET_SAMPLE_RATE, max_chunk_secs(&preset), CHUNK_OVERLAP_SECS);
+    let progress_hwnd = if chunks.len() > 1 && !preset.hide_overlay {
+        Some(crate::overlay::process::open_audio_result_window(&preset, RefineContext::Audio(audio_data.clone()), rect))
     } else {
-        Err(anyhow::anyhow!("Unsupported audio provider: {}", provider))
+        None
+    };
+
+    // Same recording + prompt + model (+ options that shape the transcript) = same transcript
+    let (cache_results, cache_ttl_hours, cache_max_entries, ui_language) = {
+        let app = APP.lock().unwrap();
+        (preset.cache_results && app.config.result_cache_max_entries > 0, app.config.result_cache_ttl_hours, app.config.result_cache_max_entries, app.config.ui_language.clone())
+    };
+    let cache_key = cache_results.then(|| {
+        // Whisper options change the transcript as much as the prompt does
+        let variant = format!("{}|{}|{}|{}|{}", preset.timestamps, preset.diarization, preset.whisper_language, preset.whisper_prompt, preset.whisper_temperature);
+        crate::result_cache::request_key(&audio_data, &preset.expanded_prompt(), &preset.model, &variant)
+    });
+    // Re-running a history entry asks for a fresh answer
+    let cached = cache_key.as_deref()
+        .filter(|_| parent_id.is_none())
+        .and_then(|key| crate::result_cache::lookup(key, cache_ttl_hours))
+        .and_then(|json| serde_json::from_str::<Transcript>(&json).ok());
+    let from_cache = cached.is_some();
+
+    let transcription_result = if let Some(transcript) = cached {
+        Ok(transcript)
+    } else if chunks.len() > 1 {
+        transcribe_in_chunks(&preset, speech_samples, &chunks, format, abort_signal, |partial| {
+            if let Some(hwnd) = progress_hwnd {
+                crate::overlay::result::update_window_text(hwnd, partial);
+            }
+        })
+    } else {
+        transcribe_audio_data(&preset, audio_data.clone())
     };
     
     unsafe {
@@ -350,45 +780,45 @@ pub fn record_audio_and_transcribe(
 
     // Check if user aborted during the API call
     if abort_signal.load(Ordering::SeqCst) {
+        if let Some(hwnd) = progress_hwnd {
+            unsafe { PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
+        }
         return;
     }
 
     match transcription_result {
-        Ok(transcription_text) => {
-            
+        Ok(transcript) => {
+            if let (Some(key), false) = (cache_key, from_cache) {
+                if let Ok(json) = serde_json::to_string(&transcript) {
+                    crate::result_cache::store(key, json, cache_ttl_hours, cache_max_entries);
+                }
+            }
+
             // SAVE HISTORY
-            {
+            let history_id = {
                 let app = crate::APP.lock().unwrap();
-                app.history.save_audio(wav_data_for_history, transcription_text.clone());
-            }
-            
-            let screen_w = unsafe { GetSystemMetrics(SM_CXSCREEN) };
-            let screen_h = unsafe { GetSystemMetrics(SM_CYSCREEN) };
-            
-            let (rect, retranslate_rect) = if preset.retranslate {
-                let w = 600;
-                let h = 300;
-                let gap = 20;
-                let total_w = w * 2 + gap;
-                let start_x = (screen_w - total_w) / 2;
-                let y = (screen_h - h) / 2;
-                
-                (
-                    RECT { left: start_x, top: y, right: start_x + w, bottom: y + h },
-                    Some(RECT { left: start_x + w + gap, top: y, right: start_x + w + gap + w, bottom: y + h })
-                )
-            } else {
-                let w = 700;
-                let h = 300;
-                let x = (screen_w - w) / 2;
-                let y = (screen_h - h) / 2;
-                (RECT { left: x, top: y, right: x + w, bottom: y + h }, None)
+                match parent_id {
+                    Some(parent_id) => app.history.save_rerun(parent_id, transcript.text.clone(), preset.model.clone(), transcript.segments.clone()),
+                    None => app.history.save_audio(audio_data_for_history, transcript.text.clone(), transcript.segments.clone()),
+                }
             };
-
-            crate::overlay::process::show_audio_result(preset, transcription_text, rect, retranslate_rect);
+            
+            // The recording stays attached to the result window for audio-aware refinement
+            let hwnd = progress_hwnd.unwrap_or_else(|| {
+                crate::overlay::process::open_audio_result_window(&preset, RefineContext::Audio(audio_data), rect)
+            });
+            if from_cache {
+                crate::overlay::result::set_window_cached(hwnd, crate::overlay::process::cached_tag(&ui_language));
+            }
+            crate::overlay::process::finish_audio_result(preset, transcript, hwnd, retranslate_rect, Some(history_id));
         },
         Err(e) => {
             eprintln!("Transcription error: {}", e);
+            if let Some(hwnd) = progress_hwnd {
+                crate::overlay::result::update_window_text(hwnd, &format!("Error: {}", e));
+            } else if parent_id.is_some() {
+                crate::overlay::process::show_rerun_error(&preset, &e.to_string());
+            }
         }
     }
 }
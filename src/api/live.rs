@@ -0,0 +1,215 @@
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+use crate::config::Preset;
+use crate::audio::vad::{Vad, VadState, trim_silence};
+use crate::audio::resample::{to_speech_format, TARGET_SAMPLE_RATE};
+use crate::audio::encode::{encode, AudioFormat};
+use crate::overlay::result::{update_window_text, RefineContext};
+use crate::overlay::utils::get_error_message;
+use super::audio::{transcribe_audio_data, audio_result_rects};
+use super::text::translate_text_streaming;
+use super::types::{Transcript, TranscriptSegment};
+
+// --- LIVE TRANSCRIPTION ---
+// Cuts the running recording into utterances and transcribes (and optionally
+// translates) each one while recording continues.
+
+const SEGMENT_SILENCE_MS: u32 = 700; // Pause that ends an utterance
+const MAX_SEGMENT_SECS: u32 = 15;    // Long monologues are cut anyway to keep latency down
+const PRE_ROLL_MS: u32 = 300;        // Audio kept before speech starts so first syllables aren't lost
+
+pub struct LiveTranscriber {
+    sample_rate: u32,
+    channels: u16,
+    vad: Vad,
+    segment: Vec<f32>, // Interleaved device samples of the current utterance
+    recorded: usize,   // Interleaved samples pushed so far, to place segments in the recording
+    tx: Option<mpsc::Sender<(Vec<f32>, f64)>>, // 16 kHz speech and its start in the recording (seconds)
+    worker: Option<JoinHandle<Transcript>>,
+    pub primary_hwnd: HWND,
+    pub secondary_hwnd: Option<HWND>,
+}
+
+impl LiveTranscriber {
+    pub fn start(preset: &Preset, sample_rate: u32, channels: u16) -> Self {
+        let (rect, retranslate_rect) = audio_result_rects(preset.retranslate);
+        // No audio to refine against until the recording is done, it is attached then
+        let primary_hwnd = crate::overlay::process::open_audio_result_window(preset, RefineContext::None, rect);
+        let secondary_hwnd = retranslate_rect
+            .map(|r| crate::overlay::process::open_live_translation_window(preset, r, primary_hwnd));
+
+        let (tx, rx) = mpsc::channel::<(Vec<f32>, f64)>();
+        let preset = preset.clone();
+
+        // Segments are handled one at a time so the transcript stays in order
+        let worker = std::thread::spawn(move || {
+            let format = AudioFormat::from_preset(&preset.audio_format);
+            let ui_language = crate::APP.lock().unwrap().config.ui_language.clone();
+            let mut transcript = Transcript::default();
+            let mut translation = String::new();
+
+            // A failed segment is reported under the text so far; the next one replaces the message
+            let show = |hwnd: HWND, text: &str, error: Option<&str>| {
+                if preset.hide_overlay { return; }
+                match error {
+                    Some(code) => {
+                        let message = get_error_message(code, &ui_language);
+                        let shown = if text.is_empty() { message } else { format!("{}\n\n{}", text, message) };
+                        update_window_text(hwnd, &shown);
+                    }
+                    None => update_window_text(hwnd, text),
+                }
+            };
+
+            while let Ok((samples, start)) = rx.recv() {
+                let data = match encode(&samples, TARGET_SAMPLE_RATE, 1, format) {
+                    Ok(d) => d,
+                    Err(e) => {
+                        eprintln!("Live segment encode error: {}", e);
+                        show(primary_hwnd, &transcript.text, Some(e.code()));
+                        continue;
+                    }
+                };
+                let result = match transcribe_audio_data(&preset, data) {
+                    Ok(t) if !t.text.trim().is_empty() => t,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        eprintln!("Live transcription error: {}", e);
+                        show(primary_hwnd, &transcript.text, Some(&e.to_string()));
+                        continue;
+                    }
+                };
+                let text = result.text.trim().to_string();
+
+                // Segment times are relative to the utterance, shift them into the recording
+                let end = start + samples.len() as f64 / TARGET_SAMPLE_RATE as f64;
+                if result.segments.is_empty() {
+                    transcript.segments.push(TranscriptSegment { start, end, text: text.clone(), speaker: String::new() });
+                } else {
+                    transcript.segments.extend(result.segments.into_iter().map(|seg| TranscriptSegment {
+                        start: start + seg.start,
+                        end: start + seg.end,
+                        ..seg
+                    }));
+                }
+
+                if !transcript.text.is_empty() { transcript.text.push(' '); }
+                transcript.text.push_str(&text);
+                show(primary_hwnd, &transcript.text, None);
+
+                if let Some(hwnd) = secondary_hwnd {
+                    let (groq_key, gemini_key) = {
+                        let app = crate::APP.lock().unwrap();
+                        (app.config.api_key.clone(), app.config.gemini_api_key.clone())
+                    };
+                    let (model_name, provider) = match crate::model_config::get_model_by_id(&preset.retranslate_model) {
+                        Some(m) => (m.full_name, m.provider),
+                        None => ("openai/gpt-oss-20b".to_string(), "groq".to_string()),
+                    };
+
+                    if !translation.is_empty() { translation.push(' '); }
+                    let base = translation.clone();
+                    let res = translate_text_streaming(
+                        &groq_key, &gemini_key, text, preset.retranslate_to.clone(),
+                        model_name, provider, preset.retranslate_streaming_enabled, false,
+                        |chunk| {
+                            translation.push_str(chunk);
+                            show(hwnd, &translation, None);
+                        }
+                    );
+                    match res {
+                        Ok(final_text) => {
+                            translation = base + final_text.trim();
+                            show(hwnd, &translation, None);
+                        }
+                        Err(e) => {
+                            eprintln!("Live translation error: {}", e);
+                            translation = base;
+                            show(hwnd, translation.trim_end(), Some(&e.to_string()));
+                        }
+                    }
+                }
+            }
+
+            transcript
+        });
+
+        Self {
+            sample_rate,
+            channels,
+            vad: Vad::new(sample_rate, channels),
+            segment: Vec::new(),
+            recorded: 0,
+            tx: Some(tx),
+            worker: Some(worker),
+            primary_hwnd,
+            secondary_hwnd,
+        }
+    }
+
+    // Feeds captured samples, sending a segment off when an utterance ends
+    pub fn push(&mut self, samples: &[f32]) {
+        let state = self.vad.process(samples);
+        self.segment.extend_from_slice(samples);
+        self.recorded += samples.len();
+
+        let frame_size = self.channels.max(1) as usize;
+        let samples_per_ms = self.sample_rate as usize * frame_size / 1000;
+
+        match state {
+            VadState::Waiting => {
+                // Nothing said yet, only keep the pre-roll
+                let keep = PRE_ROLL_MS as usize * samples_per_ms;
+                if self.segment.len() > keep * 2 {
+                    let cut = (self.segment.len() - keep) / frame_size * frame_size;
+                    self.segment.drain(..cut);
+                }
+            },
+            _ => {
+                let too_long = self.segment.len() >= MAX_SEGMENT_SECS as usize * 1000 * samples_per_ms;
+                let paused = state == VadState::Silence && self.vad.silence_ms() >= SEGMENT_SILENCE_MS;
+                if paused || too_long {
+                    self.flush();
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        let had_speech = self.vad.state() != VadState::Waiting;
+        let segment = std::mem::take(&mut self.segment);
+        self.vad.reset();
+        if !had_speech { return; }
+
+        let trimmed = trim_silence(&segment, self.sample_rate, self.channels, PRE_ROLL_MS);
+        // Its start is the silence cut from the front
+        let start_sample = self.recorded - segment.len() + trimmed.start;
+        let start = (start_sample / self.channels.max(1) as usize) as f64 / self.sample_rate as f64;
+
+        let speech = to_speech_format(&segment[trimmed], self.sample_rate, self.channels);
+        if let Some(tx) = &self.tx {
+            let _ = tx.send((speech, start));
+        }
+    }
+
+    // Discards the session (recording aborted)
+    pub fn close_windows(self) {
+        unsafe {
+            PostMessageW(self.primary_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+            if let Some(hwnd) = self.secondary_hwnd {
+                PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+
+    // Sends the last utterance and waits for all segments to be transcribed
+    pub fn finish(mut self) -> Transcript {
+        self.flush();
+        self.tx = None;
+        self.worker.take()
+            .and_then(|w| w.join().ok())
+            .unwrap_or_default()
+    }
+}
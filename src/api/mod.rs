@@ -3,7 +3,12 @@ pub mod client;
 pub mod vision;
 pub mod audio;
 pub mod text;
+pub mod live;
+pub mod tts;
+pub mod schema;
+pub mod ocr;
 
 pub use vision::translate_image_streaming;
 pub use text::{translate_text_streaming, refine_text_streaming};
+pub use types::{ChatTurn, Transcript, TranscriptSegment};
 pub use audio::record_audio_and_transcribe;
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use image::imageops::FilterType;
+use image::RgbaImage;
+use windows::core::HSTRING;
+use windows::Globalization::Language;
+use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+use windows::Media::Ocr::OcrEngine;
+use windows::Storage::Streams::DataWriter;
+use windows::Win32::System::Com::{CoInitializeEx, COINIT_MULTITHREADED};
+use crate::imaging::preprocess::PreparedImage;
+
+// --- LOCAL OCR ---
+// Offline text extraction for presets using a "local" provider model. Works
+// without an API key or network; the prompt is ignored. Pair it with the
+// retranslate step to translate the extracted text with a text model.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OcrBackend {
+    Windows, // Windows.Media.Ocr, uses the OCR language packs installed in Windows
+}
+
+impl OcrBackend {
+    // Keyed by the model's full_name in model_config
+    pub fn from_model(full_name: &str) -> Option<Self> {
+        match full_name {
+            "windows-ocr" => Some(OcrBackend::Windows),
+            _ => None,
+        }
+    }
+
+    // `language` is a BCP-47 tag ("ja", "en-US"), empty = Windows display languages
+    pub fn recognize(&self, img: &RgbaImage, language: &str) -> Result<String> {
+        match self {
+            OcrBackend::Windows => recognize_windows(img, language),
+        }
+    }
+}
+
+// Same shape as translate_image_streaming: the whole text arrives as one chunk
+pub fn recognize_image_streaming<F>(model: &str, image: &PreparedImage, language: &str, on_chunk: F) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let backend = OcrBackend::from_model(model).ok_or_else(|| anyhow::anyhow!("LOCAL_OCR_UNAVAILABLE"))?;
+    recognize_with_backend(backend, image, language, on_chunk)
+}
+
+// Also the offline fallback of cloud presets (Preset::local_ocr_fallback)
+pub fn recognize_with_backend<F>(backend: OcrBackend, image: &PreparedImage, language: &str, mut on_chunk: F) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    // Decoding the upload keeps the preset's preprocessing (upscale, threshold...) in effect
+    let img = image::load_from_memory(&image.data)?.to_rgba8();
+    let text = backend.recognize(&img, language)?;
+    if text.trim().is_empty() {
+        return Err(anyhow::anyhow!("NO_TEXT_FOUND"));
+    }
+    on_chunk(&text);
+    Ok(text)
+}
+
+fn recognize_windows(img: &RgbaImage, language: &str) -> Result<String> {
+    unsafe {
+        // WinRT needs COM on this worker thread; "already initialized" is fine
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+
+    let engine = if language.trim().is_empty() {
+        OcrEngine::TryCreateFromUserProfileLanguages()
+    } else {
+        Language::CreateLanguage(&HSTRING::from(language.trim())).and_then(|lang| OcrEngine::TryCreateFromLanguage(&lang))
+    }.map_err(|_| anyhow::anyhow!("LOCAL_OCR_UNAVAILABLE"))?;
+
+    // The engine rejects images above its limit
+    let max_dim = OcrEngine::MaxImageDimension().unwrap_or(2600);
+    let img = if img.width() > max_dim || img.height() > max_dim {
+        let scale = max_dim as f32 / img.width().max(img.height()) as f32;
+        let w = ((img.width() as f32 * scale) as u32).max(1);
+        let h = ((img.height() as f32 * scale) as u32).max(1);
+        image::imageops::resize(img, w, h, FilterType::Triangle)
+    } else {
+        img.clone()
+    };
+
+    // RGBA -> BGRA
+    let (width, height) = img.dimensions();
+    let mut bgra = img.into_raw();
+    for px in bgra.chunks_exact_mut(4) {
+        px.swap(0, 2);
+    }
+
+    let writer = DataWriter::new()?;
+    writer.WriteBytes(&bgra)?;
+    let buffer = writer.DetachBuffer()?;
+    let bitmap = SoftwareBitmap::CreateCopyFromBuffer(&buffer, BitmapPixelFormat::Bgra8, width as i32, height as i32)?;
+
+    let result = engine.RecognizeAsync(&bitmap)?.get()?;
+    let mut lines = Vec::new();
+    for line in result.Lines()? {
+        lines.push(line.Text()?.to_string_lossy());
+    }
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,108 @@
+use crate::config::{OutputField, Preset};
+
+// --- STRUCTURED OUTPUT ---
+// A preset can ask for a JSON object with user-defined keys. The keys become a
+// Groq JSON-mode instruction or a Gemini responseSchema; the reply is validated
+// and one key is shown/copied. Anything unusable falls back to the raw reply.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputSchema {
+    pub fields: Vec<OutputField>,
+    pub display_field: String,
+}
+
+impl OutputSchema {
+    // None when the option is off or no valid key is defined
+    pub fn from_preset(preset: &Preset) -> Option<Self> {
+        if !preset.structured_output {
+            return None;
+        }
+        let mut fields: Vec<OutputField> = Vec::new();
+        for field in &preset.output_fields {
+            let name = field.name.trim();
+            if is_valid_key(name) && !fields.iter().any(|f| f.name == name) {
+                fields.push(OutputField { name: name.to_string(), description: field.description.trim().to_string() });
+            }
+        }
+        let first = fields.first()?.name.clone();
+        let display = preset.output_display_field.trim();
+        let display_field = if fields.iter().any(|f| f.name == display) { display.to_string() } else { first };
+        Some(Self { fields, display_field })
+    }
+
+    // Groq JSON mode only guarantees valid JSON, so the keys are spelled out in the prompt
+    pub fn prompt_instruction(&self) -> String {
+        let keys = self.fields.iter()
+            .map(|f| if f.description.is_empty() { format!("\"{}\"", f.name) } else { format!("\"{}\" ({})", f.name, f.description) })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("\n\nRespond with a single JSON object with exactly these string keys: {}.", keys)
+    }
+
+    pub fn groq_response_format(&self) -> serde_json::Value {
+        serde_json::json!({ "type": "json_object" })
+    }
+
+    // Goes into Gemini's generationConfig
+    pub fn gemini_generation_config(&self) -> serde_json::Value {
+        let properties: serde_json::Map<String, serde_json::Value> = self.fields.iter().map(|f| {
+            let mut prop = serde_json::json!({ "type": "STRING" });
+            if !f.description.is_empty() {
+                prop["description"] = serde_json::json!(f.description);
+            }
+            (f.name.clone(), prop)
+        }).collect();
+        let required: Vec<&str> = self.fields.iter().map(|f| f.name.as_str()).collect();
+
+        serde_json::json!({
+            "responseMimeType": "application/json",
+            "responseSchema": {
+                "type": "OBJECT",
+                "properties": properties,
+                "required": required,
+                "propertyOrdering": required
+            }
+        })
+    }
+
+    // Text to show/copy. Missing keys are tolerated as long as the display key is
+    // there; otherwise (or when the reply is not a JSON object) the reply is kept.
+    pub fn extract(&self, reply: &str) -> String {
+        let obj = match parse_object(reply) {
+            Some(obj) => obj,
+            None => {
+                eprintln!("Structured output: reply is not a JSON object, showing it as text");
+                return reply.to_string();
+            }
+        };
+
+        let missing: Vec<&str> = self.fields.iter().map(|f| f.name.as_str()).filter(|k| !obj.contains_key(*k)).collect();
+        if !missing.is_empty() {
+            eprintln!("Structured output: missing keys {:?}", missing);
+        }
+
+        match obj.get(&self.display_field) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Null) | None => reply.to_string(),
+            Some(other) => other.to_string(),
+        }
+    }
+}
+
+// Letters, digits, '_' and '-' (what both APIs accept as property names)
+fn is_valid_key(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 64 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// Tolerates markdown fences and text around the object
+fn parse_object(reply: &str) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end <= start {
+        return None;
+    }
+    match serde_json::from_str(&reply[start..=end]).ok()? {
+        serde_json::Value::Object(obj) => Some(obj),
+        _ => None,
+    }
+}
@@ -3,8 +3,9 @@ use std::io::{BufRead, BufReader};
 use crate::APP;
 use crate::overlay::result::RefineContext;
 use super::client::UREQ_AGENT;
-use super::types::{StreamChunk, ChatCompletionResponse};
-use super::vision::translate_image_streaming;
+use base64::{Engine as _, engine::general_purpose};
+use super::types::{StreamChunk, ChatCompletionResponse, ChatTurn};
+use crate::audio::encode::AudioFormat;
 
 pub fn translate_text_streaming<F>(
     groq_api_key: &str,
@@ -207,13 +208,40 @@ where
     Ok(full_content)
 }
 
+// Turns the stored conversation into API messages. The original result (leading
+// assistant turn) is folded into the first instruction so the model sees what it
+// is refining, the same way the old one-shot prompt did.
+fn build_refine_messages(conversation: &[ChatTurn]) -> Vec<ChatTurn> {
+    let mut messages = Vec::new();
+    let mut turns = conversation.iter().peekable();
+
+    let original = match turns.peek() {
+        Some(t) if t.role == "assistant" => turns.next().map(|t| t.content.clone()),
+        _ => None,
+    };
+
+    if let Some(first) = turns.next() {
+        let content = match &original {
+            Some(text) if !text.trim().is_empty() => format!(
+                "Content:\n{}\n\nInstruction:\n{}\n\nOutput ONLY the result.",
+                text, first.content
+            ),
+            _ => first.content.clone(),
+        };
+        messages.push(ChatTurn::user(content));
+    }
+
+    messages.extend(turns.cloned());
+    messages
+}
+
 // NEW: Refinement API with model-aware and context-aware handling
+// `conversation` holds the whole thread of the result window, ending with the new instruction.
 pub fn refine_text_streaming<F>(
     groq_api_key: &str,
     gemini_api_key: &str,
     context: RefineContext,
-    previous_text: String,
-    user_prompt: String,
+    conversation: &[ChatTurn],
     original_model_id: &str,
     original_provider: &str,
     streaming_enabled: bool,
@@ -222,20 +250,37 @@ pub fn refine_text_streaming<F>(
 where
     F: FnMut(&str),
 {
-    // 1. CONSTRUCT SIMPLE PROMPT
-    let final_prompt = format!(
-        "Content:\n{}\n\nInstruction:\n{}\n\nOutput ONLY the result.",
-        previous_text, user_prompt
-    );
+    // 1. BUILD CHAT MESSAGES
+    let messages = build_refine_messages(conversation);
+    if messages.is_empty() {
+        return Err(anyhow::anyhow!("Nothing to refine"));
+    }
 
     // 2. Determine the Base Model ID/Name and Provider we WANT to use
     let (mut target_id_or_name, mut target_provider) = match context {
+        RefineContext::Image(_) if original_provider == "local" => {
+            // Offline OCR can't follow instructions, refinements go to a cloud vision model
+            if !gemini_api_key.trim().is_empty() {
+                ("gemini-flash-lite".to_string(), "google".to_string())
+            } else {
+                ("maverick".to_string(), "groq".to_string())
+            }
+        },
         RefineContext::Image(_) => {
             // For images, we try to stick to the original vision model.
             (original_model_id.to_string(), original_provider.to_string())
         },
+        RefineContext::Audio(_) if !gemini_api_key.trim().is_empty() => {
+            // Transcripts go back to a Gemini audio model together with the recording.
+            // Whisper can't take instructions, so Groq transcripts switch to Gemini.
+            if original_provider == "google" {
+                (original_model_id.to_string(), original_provider.to_string())
+            } else {
+                ("gemini-audio".to_string(), "google".to_string())
+            }
+        },
         _ => {
-            // RefineContext::None (Retranslate) or RefineContext::Audio (Transcript Refinement)
+            // RefineContext::None (Retranslate) or RefineContext::Audio without a Gemini key (Transcript Refinement)
             // Force smart text model: prioritize Google if key present, else Groq
             if !gemini_api_key.trim().is_empty() {
                  ("gemini-flash-lite".to_string(), "google".to_string()) 
@@ -253,131 +298,139 @@ where
         target_id_or_name = conf.full_name;
         target_provider = conf.provider; // Also ensure provider matches config
     }
-    
-    // Helper closure to execute Text-Only generation using final_prompt
-    let mut exec_text_only = |p_model: String, p_provider: String| -> Result<String> {
-        let mut full_content = String::new();
 
-        if p_provider == "google" {
-             if gemini_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_GEMINI_KEY")); }
-             
-             let method = if streaming_enabled { "streamGenerateContent" } else { "generateContent" };
-             let url = if streaming_enabled {
-                 format!("https://generativelanguage.googleapis.com/v1beta/models/{}:{}?alt=sse", p_model, method)
-             } else {
-                 format!("https://generativelanguage.googleapis.com/v1beta/models/{}:{}", p_model, method)
-             };
-
-             let payload = serde_json::json!({
-                 "contents": [{ "role": "user", "parts": [{ "text": final_prompt }] }]
-             });
-
-             let resp = UREQ_AGENT.post(&url)
-                 .set("x-goog-api-key", gemini_api_key)
-                 .send_json(payload)
-                 .map_err(|e| anyhow::anyhow!("Gemini Refine Error: {}", e))?;
-
-             if streaming_enabled {
-                 let reader = BufReader::new(resp.into_reader());
-                 for line in reader.lines() {
-                     let line = line?;
-                     if line.starts_with("data: ") {
-                         let json_str = &line["data: ".len()..];
-                         if json_str.trim() == "[DONE]" { break; }
-                         if let Ok(chunk_resp) = serde_json::from_str::<serde_json::Value>(json_str) {
-                             if let Some(candidates) = chunk_resp.get("candidates").and_then(|c| c.as_array()) {
-                                 if let Some(first) = candidates.first() {
-                                     if let Some(parts) = first.get("content").and_then(|c| c.get("parts")).and_then(|p| p.as_array()) {
-                                         if let Some(p) = parts.first() {
-                                             if let Some(t) = p.get("text").and_then(|v| v.as_str()) {
-                                                 full_content.push_str(t);
-                                                 on_chunk(t);
-                                             }
-                                         }
-                                     }
-                                 }
-                             }
-                         }
-                     }
-                 }
-             } else {
-                 let json: serde_json::Value = resp.into_json()?;
-                 if let Some(candidates) = json.get("candidates").and_then(|c| c.as_array()) {
-                     if let Some(first) = candidates.first() {
-                         if let Some(parts) = first.get("content").and_then(|c| c.get("parts")).and_then(|p| p.as_array()) {
-                            full_content = parts.iter().filter_map(|p| p.get("text").and_then(|t| t.as_str())).collect::<String>();
-                            on_chunk(&full_content);
-                         }
-                     }
-                 }
-             }
-        } else {
-            // Groq
-            if groq_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_API_KEY")); }
-            
-            let payload = serde_json::json!({
-                "model": p_model,
-                "messages": [{ "role": "user", "content": final_prompt }],
-                "stream": streaming_enabled
-            });
-            
-            let resp = UREQ_AGENT.post("https://api.groq.com/openai/v1/chat/completions")
-                .set("Authorization", &format!("Bearer {}", groq_api_key))
-                .send_json(payload)
-                .map_err(|e| anyhow::anyhow!("Groq Refine Error: {}", e))?;
-
-            // Capture Rate Limits
-            if let Some(remaining) = resp.header("x-ratelimit-remaining-requests") {
-                 let limit = resp.header("x-ratelimit-limit-requests").unwrap_or("?");
-                 let usage_str = format!("{} / {}", remaining, limit);
-                 if let Ok(mut app) = APP.lock() {
-                     app.model_usage_stats.insert(p_model.clone(), usage_str);
-                 }
+    // The image/recording (if any) travels with the first user message.
+    // Only Gemini accepts audio, Groq refinements of transcripts stay text-only.
+    let attachment = match &context {
+        RefineContext::Image(img_bytes) => Some(("image/png", general_purpose::STANDARD.encode(img_bytes))),
+        RefineContext::Audio(audio_bytes) if target_provider == "google" && !audio_bytes.is_empty() => Some((AudioFormat::detect(audio_bytes).mime_type(), general_purpose::STANDARD.encode(audio_bytes))),
+        _ => None,
+    };
+
+    let mut full_content = String::new();
+
+    if target_provider == "google" {
+        if gemini_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_GEMINI_KEY")); }
+
+        let contents: Vec<serde_json::Value> = messages.iter().enumerate().map(|(i, m)| {
+            let role = if m.role == "assistant" { "model" } else { "user" };
+            let mut parts = vec![serde_json::json!({ "text": m.content })];
+            if i == 0 {
+                if let Some((mime_type, b64)) = &attachment {
+                    parts.push(serde_json::json!({ "inline_data": { "mime_type": mime_type, "data": b64 } }));
+                }
             }
+            serde_json::json!({ "role": role, "parts": parts })
+        }).collect();
 
-            if streaming_enabled {
-                let reader = BufReader::new(resp.into_reader());
-                for line in reader.lines() {
-                    let line = line?;
-                    if line.starts_with("data: ") {
-                         let data = &line[6..];
-                         if data == "[DONE]" { break; }
-                         if let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) {
-                             if let Some(content) = chunk.choices.get(0).and_then(|c| c.delta.content.as_ref()) {
-                                 full_content.push_str(content);
-                                 on_chunk(content);
-                             }
-                         }
+        let method = if streaming_enabled { "streamGenerateContent" } else { "generateContent" };
+        let url = if streaming_enabled {
+            format!("https://generativelanguage.googleapis.com/v1beta/models/{}:{}?alt=sse", target_id_or_name, method)
+        } else {
+            format!("https://generativelanguage.googleapis.com/v1beta/models/{}:{}", target_id_or_name, method)
+        };
+
+        let payload = serde_json::json!({ "contents": contents });
+
+        let resp = UREQ_AGENT.post(&url)
+            .set("x-goog-api-key", gemini_api_key)
+            .send_json(payload)
+            .map_err(|e| anyhow::anyhow!("Gemini Refine Error: {}", e))?;
+
+        if streaming_enabled {
+            let reader = BufReader::new(resp.into_reader());
+            for line in reader.lines() {
+                let line = line?;
+                if line.starts_with("data: ") {
+                    let json_str = &line["data: ".len()..];
+                    if json_str.trim() == "[DONE]" { break; }
+                    if let Ok(chunk_resp) = serde_json::from_str::<serde_json::Value>(json_str) {
+                        if let Some(candidates) = chunk_resp.get("candidates").and_then(|c| c.as_array()) {
+                            if let Some(first) = candidates.first() {
+                                if let Some(parts) = first.get("content").and_then(|c| c.get("parts")).and_then(|p| p.as_array()) {
+                                    if let Some(p) = parts.first() {
+                                        if let Some(t) = p.get("text").and_then(|v| v.as_str()) {
+                                            full_content.push_str(t);
+                                            on_chunk(t);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            let json: serde_json::Value = resp.into_json()?;
+            if let Some(candidates) = json.get("candidates").and_then(|c| c.as_array()) {
+                if let Some(first) = candidates.first() {
+                    if let Some(parts) = first.get("content").and_then(|c| c.get("parts")).and_then(|p| p.as_array()) {
+                        full_content = parts.iter().filter_map(|p| p.get("text").and_then(|t| t.as_str())).collect::<String>();
+                        on_chunk(&full_content);
                     }
                 }
-            } else {
-                 let json: ChatCompletionResponse = resp.into_json()?;
-                 if let Some(choice) = json.choices.first() {
-                     full_content = choice.message.content.clone();
-                     on_chunk(&full_content);
-                 }
             }
         }
+    } else {
+        // Groq
+        if groq_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_API_KEY")); }
+
+        let api_messages: Vec<serde_json::Value> = messages.iter().enumerate().map(|(i, m)| {
+            match (&attachment, i) {
+                (Some((mime_type, b64)), 0) if *mime_type == "image/png" => serde_json::json!({
+                    "role": m.role,
+                    "content": [
+                        { "type": "text", "text": m.content },
+                        { "type": "image_url", "image_url": { "url": format!("data:image/png;base64,{}", b64) } }
+                    ]
+                }),
+                _ => serde_json::json!({ "role": m.role, "content": m.content }),
+            }
+        }).collect();
+
+        let payload = serde_json::json!({
+            "model": target_id_or_name,
+            "messages": api_messages,
+            "stream": streaming_enabled
+        });
         
-        Ok(full_content)
-    };
+        let resp = UREQ_AGENT.post("https://api.groq.com/openai/v1/chat/completions")
+            .set("Authorization", &format!("Bearer {}", groq_api_key))
+            .send_json(payload)
+            .map_err(|e| anyhow::anyhow!("Groq Refine Error: {}", e))?;
 
-    match context {
-        RefineContext::Image(img_bytes) => {
-            if target_provider == "google" {
-                if gemini_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_GEMINI_KEY")); }
-                let img = image::load_from_memory(&img_bytes)?.to_rgba8();
-                translate_image_streaming(groq_api_key, gemini_api_key, final_prompt, target_id_or_name, target_provider, img, streaming_enabled, false, on_chunk)
-            } else {
-                // Groq/Llama Vision
-                if groq_api_key.trim().is_empty() { return Err(anyhow::anyhow!("NO_API_KEY")); }
-                let img = image::load_from_memory(&img_bytes)?.to_rgba8();
-                translate_image_streaming(groq_api_key, gemini_api_key, final_prompt, target_id_or_name, target_provider, img, streaming_enabled, false, on_chunk)
+        // Capture Rate Limits
+        if let Some(remaining) = resp.header("x-ratelimit-remaining-requests") {
+             let limit = resp.header("x-ratelimit-limit-requests").unwrap_or("?");
+             let usage_str = format!("{} / {}", remaining, limit);
+             if let Ok(mut app) = APP.lock() {
+                 app.model_usage_stats.insert(target_id_or_name.clone(), usage_str);
+             }
+        }
+
+        if streaming_enabled {
+            let reader = BufReader::new(resp.into_reader());
+            for line in reader.lines() {
+                let line = line?;
+                if line.starts_with("data: ") {
+                     let data = &line[6..];
+                     if data == "[DONE]" { break; }
+                     if let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) {
+                         if let Some(content) = chunk.choices.get(0).and_then(|c| c.delta.content.as_ref()) {
+                             full_content.push_str(content);
+                             on_chunk(content);
+                         }
+                     }
+                }
             }
-        },
-        RefineContext::None => {
-            // Text Only - Use the helper to execute with final_prompt
-            exec_text_only(target_id_or_name, target_provider)
+        } else {
+             let json: ChatCompletionResponse = resp.into_json()?;
+             if let Some(choice) = json.choices.first() {
+                 full_content = choice.message.content.clone();
+                 on_chunk(&full_content);
+             }
         }
     }
+
+    Ok(full_content)
 }
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
+use std::io::Read;
+use crate::audio::encode::encode_wav;
+use crate::config::Config;
+use super::client::UREQ_AGENT;
+
+// --- TEXT TO SPEECH ---
+// Result text is read aloud through the provider chosen in the global settings.
+// Generated audio is cached as WAV in history_media, keyed by provider, voice
+// and text, so replaying a result does not call the API again. The cache is kept
+// under a size cap, least recently played files go first.
+
+const GEMINI_TTS_MODEL: &str = "gemini-2.5-flash-preview-tts";
+const GEMINI_TTS_SAMPLE_RATE: u32 = 24000;
+const TTS_CACHE_MAX_BYTES: u64 = 100 * 1024 * 1024;
+const TTS_CACHE_MAX_AGE_DAYS: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TtsProvider {
+    Gemini,
+    OpenAi, // Any OpenAI-compatible /audio/speech endpoint
+    Local,
+}
+
+impl TtsProvider {
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "openai" => TtsProvider::OpenAi,
+            "local" => TtsProvider::Local,
+            _ => TtsProvider::Gemini,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            TtsProvider::Gemini => "gemini",
+            TtsProvider::OpenAi => "openai",
+            TtsProvider::Local => "local",
+        }
+    }
+
+    pub fn default_voice(&self) -> &'static str {
+        match self {
+            TtsProvider::Gemini => "Kore",
+            TtsProvider::OpenAi => "alloy",
+            TtsProvider::Local => "",
+        }
+    }
+
+    // Returns WAV bytes
+    pub fn synthesize(&self, config: &Config, text: &str, voice: &str) -> Result<Vec<u8>> {
+        match self {
+            TtsProvider::Gemini => synthesize_gemini(&config.gemini_api_key, text, voice),
+            TtsProvider::OpenAi => synthesize_openai(&config.tts_openai_url, &config.tts_openai_api_key, &config.tts_openai_model, text, voice),
+            TtsProvider::Local => Err(anyhow::anyhow!("LOCAL_TTS_NOT_AVAILABLE")),
+        }
+    }
+}
+
+// Cached speech for `text` with the current settings, synthesized on a miss
+pub fn speech_for_text(text: &str) -> Result<Vec<u8>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(anyhow::anyhow!("NO_TEXT_TO_SPEAK"));
+    }
+
+    let config = crate::APP.lock().unwrap().config.clone();
+    let provider = TtsProvider::from_config(&config.tts_provider);
+    let voice = if config.tts_voice.trim().is_empty() { provider.default_voice().to_string() } else { config.tts_voice.trim().to_string() };
+
+    let cache_path = crate::history::get_media_path(&format!("tts_{:016x}.wav", cache_key(provider, &voice, text)));
+    if let Ok(cached) = std::fs::read(&cache_path) {
+        // Modified time doubles as "last played" for pruning
+        if let Ok(file) = std::fs::File::options().append(true).open(&cache_path) {
+            let _ = file.set_modified(std::time::SystemTime::now());
+        }
+        return Ok(cached);
+    }
+
+    let wav = provider.synthesize(&config, text, &voice)?;
+    if let Err(e) = std::fs::write(&cache_path, &wav) {
+        eprintln!("Failed to cache speech audio: {}", e);
+    }
+    prune_speech_cache();
+    Ok(wav)
+}
+
+// Drops cached speech not played for TTS_CACHE_MAX_AGE_DAYS, then the least
+// recently played files until the rest fits in TTS_CACHE_MAX_BYTES
+fn prune_speech_cache() {
+    let dir = crate::history::get_media_path("");
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let max_age = std::time::Duration::from_secs(TTS_CACHE_MAX_AGE_DAYS * 24 * 3600);
+    let now = std::time::SystemTime::now();
+
+    let mut files: Vec<(std::path::PathBuf, std::time::SystemTime, u64)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.starts_with("tts_") && name.ends_with(".wav")
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some((e.path(), meta.modified().ok()?, meta.len()))
+        })
+        .collect();
+
+    // Newest first, so whatever is past the limits is at the end
+    files.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut total = 0;
+    for (path, modified, size) in files {
+        total += size;
+        let too_old = now.duration_since(modified).is_ok_and(|age| age > max_age);
+        if too_old || total > TTS_CACHE_MAX_BYTES {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn cache_key(provider: TtsProvider, voice: &str, text: &str) -> u64 {
+    crate::result_cache::content_hash(&[provider.id().as_bytes(), voice.as_bytes(), text.as_bytes()])
+}
+
+fn map_http_error(e: ureq::Error, api: &str) -> anyhow::Error {
+    let err_str = e.to_string();
+    if err_str.contains("401") || err_str.contains("403") {
+        anyhow::anyhow!("INVALID_API_KEY")
+    } else {
+        anyhow::anyhow!("{} Error: {}", api, err_str)
+    }
+}
+
+fn synthesize_gemini(gemini_api_key: &str, text: &str, voice: &str) -> Result<Vec<u8>> {
+    if gemini_api_key.trim().is_empty() {
+        return Err(anyhow::anyhow!("NO_API_KEY"));
+    }
+
+    let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent", GEMINI_TTS_MODEL);
+    let payload = serde_json::json!({
+        "contents": [{
+            "role": "user",
+            "parts": [{ "text": text }]
+        }],
+        "generationConfig": {
+            "responseModalities": ["AUDIO"],
+            "speechConfig": {
+                "voiceConfig": {
+                    "prebuiltVoiceConfig": { "voiceName": voice }
+                }
+            }
+        }
+    });
+
+    let resp: serde_json::Value = UREQ_AGENT.post(&url)
+        .set("x-goog-api-key", gemini_api_key)
+        .send_json(payload)
+        .map_err(|e| map_http_error(e, "Gemini TTS"))?
+        .into_json()?;
+
+    let inline = resp.get("candidates")
+        .and_then(|c| c.get(0))
+        .and_then(|c| c.get("content"))
+        .and_then(|c| c.get("parts"))
+        .and_then(|p| p.as_array())
+        .and_then(|parts| parts.iter().find_map(|p| p.get("inlineData").or_else(|| p.get("inline_data"))))
+        .ok_or_else(|| anyhow::anyhow!("No audio received from Gemini TTS"))?;
+
+    let data = inline.get("data").and_then(|d| d.as_str()).unwrap_or_default();
+    let pcm = general_purpose::STANDARD.decode(data)?;
+
+    // Raw 16-bit mono PCM, e.g. "audio/L16;codec=pcm;rate=24000"
+    let sample_rate = inline.get("mimeType").and_then(|m| m.as_str())
+        .and_then(|m| m.split(';').find_map(|p| p.trim().strip_prefix("rate=")))
+        .and_then(|r| r.parse().ok())
+        .unwrap_or(GEMINI_TTS_SAMPLE_RATE);
+    let samples: Vec<f32> = pcm.chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+        .collect();
+
+    encode_wav(&samples, sample_rate, 1).map_err(|e| anyhow::anyhow!(e.code()))
+}
+
+fn synthesize_openai(base_url: &str, api_key: &str, model: &str, text: &str, voice: &str) -> Result<Vec<u8>> {
+    let url = format!("{}/audio/speech", base_url.trim().trim_end_matches('/'));
+    let payload = serde_json::json!({
+        "model": model,
+        "input": text,
+        "voice": voice,
+        "response_format": "wav"
+    });
+
+    // Local servers often run without a key
+    let mut req = UREQ_AGENT.post(&url);
+    if !api_key.trim().is_empty() {
+        req = req.set("Authorization", &format!("Bearer {}", api_key.trim()));
+    }
+    let resp = req.send_json(payload).map_err(|e| map_http_error(e, "TTS"))?;
+
+    let mut wav = Vec::new();
+    resp.into_reader().read_to_end(&mut wav)?;
+    if !wav.starts_with(b"RIFF") {
+        return Err(anyhow::anyhow!("TTS endpoint did not return WAV audio"));
+    }
+    Ok(wav)
+}
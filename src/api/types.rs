@@ -29,3 +29,345 @@ pub struct ChatChoice {
 pub struct ChatMessage {
     pub content: String,
 }
+
+// One message of a refine conversation in a result window.
+// `role` is "user" or "assistant" (mapped to "model" for Gemini).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChatTurn {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatTurn {
+    pub fn user(content: String) -> Self {
+        Self { role: "user".to_string(), content }
+    }
+
+    pub fn assistant(content: String) -> Self {
+        Self { role: "assistant".to_string(), content }
+    }
+}
+
+// A timed piece of an audio transcript, in seconds from the start of the recording
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    // Speaker label from diarization, empty otherwise
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub speaker: String,
+}
+
+// Transcription result. `segments` is only filled when timestamps were requested.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Transcript {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    pub fn plain(text: String) -> Self {
+        Self { text, segments: Vec::new() }
+    }
+
+    // Text for the result window: one "[mm:ss] ..." line per segment when timestamps exist,
+    // "[mm:ss] Speaker: ..." when diarized
+    pub fn display_text(&self) -> String {
+        if self.segments.is_empty() {
+            return self.text.clone();
+        }
+        self.segments.iter()
+            .map(|s| if s.speaker.is_empty() {
+                format!("[{}] {}", format_timestamp(s.start), s.text.trim())
+            } else {
+                format!("[{}] {}: {}", format_timestamp(s.start), s.speaker, s.text.trim())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Distinct speaker labels in order of first appearance
+    pub fn speakers(&self) -> Vec<String> {
+        speakers_of(&self.segments)
+    }
+
+    // Parses Gemini's speaker-labelled JSON ([{speaker, start, end, text}], possibly in a
+    // ```json fence). Times may be seconds or "mm:ss". Falls back to plain text.
+    pub fn from_diarized_json(text: String) -> Self {
+        let json_part = match (text.find('['), text.rfind(']')) {
+            (Some(start), Some(end)) if end > start => &text[start..=end],
+            _ => return Self::plain(text),
+        };
+        let items: Vec<serde_json::Value> = match serde_json::from_str(json_part) {
+            Ok(items) => items,
+            Err(_) => return Self::plain(text),
+        };
+
+        let time = |v: Option<&serde_json::Value>| -> Option<f64> {
+            match v? {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => s.trim().parse().ok().or_else(|| parse_timestamp(s)),
+                _ => None,
+            }
+        };
+        let segments: Vec<TranscriptSegment> = items.iter().filter_map(|item| {
+            let text = item.get("text")?.as_str()?.trim().to_string();
+            if text.is_empty() { return None; }
+            let start = time(item.get("start")).unwrap_or(0.0);
+            Some(TranscriptSegment {
+                start,
+                end: time(item.get("end")).unwrap_or(start).max(start),
+                text,
+                speaker: item.get("speaker").and_then(|s| s.as_str()).unwrap_or("").trim().to_string(),
+            })
+        }).collect();
+
+        if segments.is_empty() {
+            return Self::plain(text);
+        }
+        Self { text: diarized_text(&segments), segments }
+    }
+
+    // Parses "[mm:ss] text" lines (timestamped Gemini output). A segment ends where the
+    // next one starts; the last one gets an estimate from its length.
+    pub fn from_timestamped(text: String) -> Self {
+        let mut segments: Vec<TranscriptSegment> = Vec::new();
+        for line in text.lines() {
+            let line = line.trim();
+            let parsed = line.strip_prefix('[')
+                .and_then(|rest| rest.split_once(']'))
+                .and_then(|(stamp, body)| parse_timestamp(stamp).map(|start| (start, body.trim())));
+            match parsed {
+                Some((start, body)) => {
+                    if let Some(prev) = segments.last_mut() {
+                        prev.end = start.max(prev.start);
+                    }
+                    segments.push(TranscriptSegment { start, end: start, text: body.to_string(), speaker: String::new() });
+                },
+                // Continuation lines belong to the previous segment
+                None if !line.is_empty() => {
+                    if let Some(prev) = segments.last_mut() {
+                        prev.text.push(' ');
+                        prev.text.push_str(line);
+                    }
+                },
+                None => {}
+            }
+        }
+        if let Some(last) = segments.last_mut() {
+            last.end = last.start + (last.text.chars().count() as f64 / 15.0).max(2.0);
+        }
+
+        if segments.is_empty() {
+            return Self::plain(text);
+        }
+        let plain = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+        Self { text: plain, segments }
+    }
+}
+
+// One text block of an in-place ("screen translation") result. The box is
+// relative to the captured region: 0.0-1.0 from its left/top edge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OcrBlock {
+    pub text: String,
+    pub translation: String,
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+// Parses a `[{"text", "translation", "bbox": [ymin, xmin, ymax, xmax]}]` reply with
+// coordinates normalized to 0-1000 (Gemini's convention; 0-1 is accepted too).
+// Blocks without text or with an unusable box are dropped; None when nothing is left.
+pub fn parse_ocr_blocks(reply: &str) -> Option<Vec<OcrBlock>> {
+    let json_part = match (reply.find('['), reply.rfind(']')) {
+        (Some(start), Some(end)) if end > start => &reply[start..=end],
+        _ => return None,
+    };
+    let items: Vec<serde_json::Value> = serde_json::from_str(json_part).ok()?;
+
+    let blocks: Vec<OcrBlock> = items.iter().filter_map(|item| {
+        let text = item.get("text").and_then(|t| t.as_str()).unwrap_or("").trim().to_string();
+        let translation = item.get("translation").and_then(|t| t.as_str()).unwrap_or("").trim().to_string();
+        if text.is_empty() && translation.is_empty() {
+            return None;
+        }
+
+        let bbox: Vec<f32> = item.get("bbox")?.as_array()?.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect();
+        if bbox.len() != 4 {
+            return None;
+        }
+        let scale = if bbox.iter().all(|v| *v <= 1.0) { 1.0 } else { 1000.0 };
+        let [top, left, bottom, right] = [bbox[0], bbox[1], bbox[2], bbox[3]].map(|v| (v / scale).clamp(0.0, 1.0));
+        if right <= left || bottom <= top {
+            return None;
+        }
+
+        Some(OcrBlock {
+            translation: if translation.is_empty() { text.clone() } else { translation },
+            text,
+            left, top, right, bottom,
+        })
+    }).collect();
+
+    if blocks.is_empty() { None } else { Some(blocks) }
+}
+
+// Reading order (top to bottom, then left to right) for copy/history
+pub fn blocks_text(blocks: &[OcrBlock]) -> String {
+    let mut ordered: Vec<&OcrBlock> = blocks.iter().collect();
+    ordered.sort_by(|a, b| a.top.partial_cmp(&b.top).unwrap_or(std::cmp::Ordering::Equal)
+        .then(a.left.partial_cmp(&b.left).unwrap_or(std::cmp::Ordering::Equal)));
+    ordered.iter().map(|b| b.translation.as_str()).collect::<Vec<_>>().join("\n")
+}
+
+// "Speaker: text" lines of a diarized transcript
+pub fn diarized_text(segments: &[TranscriptSegment]) -> String {
+    segments.iter()
+        .map(|s| if s.speaker.is_empty() { s.text.clone() } else { format!("{}: {}", s.speaker, s.text) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn speakers_of(segments: &[TranscriptSegment]) -> Vec<String> {
+    let mut speakers: Vec<String> = Vec::new();
+    for seg in segments {
+        if !seg.speaker.is_empty() && !speakers.contains(&seg.speaker) {
+            speakers.push(seg.speaker.clone());
+        }
+    }
+    speakers
+}
+
+// Index of the speaker a "[mm:ss] Speaker: ..." result line starts with, and the byte
+// length of that label (up to and including the colon)
+pub fn speaker_label(line: &str, speakers: &[String]) -> Option<(usize, usize)> {
+    let rest_start = match line.strip_prefix('[').and_then(|r| r.find(']')) {
+        Some(close) => close + 2,
+        None => 0,
+    };
+    let rest = line.get(rest_start..)?.trim_start();
+    let skipped = line.len() - rest.len();
+    speakers.iter().position(|sp| rest.starts_with(sp.as_str()) && rest[sp.len()..].starts_with(':'))
+        .map(|i| (i, skipped + speakers[i].len() + 1))
+}
+
+// "mm:ss" or "h:mm:ss" back to seconds
+pub fn parse_timestamp(stamp: &str) -> Option<f64> {
+    let parts: Vec<&str> = stamp.trim().split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    let mut secs = 0.0;
+    for part in parts {
+        let value: f64 = part.trim().parse().ok()?;
+        secs = secs * 60.0 + value;
+    }
+    Some(secs)
+}
+
+pub fn format_timestamp(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diarized_json_in_a_fence() {
+        let reply = "```json\n[\n  {\"speaker\": \"Speaker 1\", \"start\": 0, \"end\": 2.5, \"text\": \"Hello.\"},\n  {\"speaker\": \"Speaker 2\", \"start\": 2.5, \"end\": 4, \"text\": \" Hi there \"}\n]\n```";
+        let t = Transcript::from_diarized_json(reply.to_string());
+        assert_eq!(t.segments.len(), 2);
+        assert_eq!(t.segments[1].speaker, "Speaker 2");
+        assert_eq!(t.segments[1].text, "Hi there");
+        assert_eq!((t.segments[0].start, t.segments[0].end), (0.0, 2.5));
+        assert_eq!(t.text, "Speaker 1: Hello.\nSpeaker 2: Hi there");
+        assert_eq!(t.speakers(), vec!["Speaker 1", "Speaker 2"]);
+    }
+
+    #[test]
+    fn diarized_json_string_timestamps() {
+        let reply = r#"[{"speaker": "A", "start": "1:05", "end": "01:07.5", "text": "one"},
+                        {"speaker": "B", "start": "70.25", "text": "two"},
+                        {"speaker": "A", "start": "nonsense", "end": 3, "text": "three"},
+                        {"speaker": "B", "start": 9, "end": 8, "text": "four"},
+                        {"speaker": "A", "start": 10, "text": ""}]"#;
+        let t = Transcript::from_diarized_json(reply.to_string());
+        let times: Vec<(f64, f64)> = t.segments.iter().map(|s| (s.start, s.end)).collect();
+        // Missing or unreadable times fall back to 0 / the start; empty utterances are dropped
+        assert_eq!(times, vec![(65.0, 67.5), (70.25, 70.25), (0.0, 3.0), (9.0, 9.0)]);
+    }
+
+    #[test]
+    fn diarized_json_falls_back_to_plain_text() {
+        for reply in ["Just a plain transcript.", "[not json at all]", "[]", r#"[{"speaker": "A"}]"#] {
+            let t = Transcript::from_diarized_json(reply.to_string());
+            assert!(t.segments.is_empty(), "{}", reply);
+            assert_eq!(t.text, reply);
+        }
+    }
+
+    #[test]
+    fn timestamped_lines() {
+        let t = Transcript::from_timestamped("[00:01] First line\ncontinued\n[01:02] Second".to_string());
+        assert_eq!(t.segments.len(), 2);
+        assert_eq!((t.segments[0].start, t.segments[0].end), (1.0, 62.0));
+        assert_eq!(t.segments[0].text, "First line continued");
+        assert_eq!(t.text, "First line continued Second");
+        assert_eq!(t.display_text(), "[00:01] First line continued\n[01:02] Second");
+    }
+
+    #[test]
+    fn ocr_blocks_from_a_valid_reply() {
+        let reply = "```json\n[{\"text\": \"Start\", \"translation\": \"Bắt đầu\", \"bbox\": [100, 50, 200, 450]},\n {\"text\": \"Quit\", \"translation\": \"\", \"bbox\": [0.5, 0.1, 0.6, 0.3]}]\n```";
+        let blocks = parse_ocr_blocks(reply).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], OcrBlock { text: "Start".into(), translation: "Bắt đầu".into(), left: 0.05, top: 0.1, right: 0.45, bottom: 0.2 });
+        // Already normalized boxes are kept, a missing translation falls back to the text
+        assert_eq!((blocks[1].left, blocks[1].top, blocks[1].right, blocks[1].bottom), (0.1, 0.5, 0.3, 0.6));
+        assert_eq!(blocks[1].translation, "Quit");
+        assert_eq!(blocks_text(&blocks), "Bắt đầu\nQuit");
+    }
+
+    #[test]
+    fn ocr_blocks_drop_unusable_items() {
+        let reply = r#"[
+            {"text": "ok", "translation": "fine", "bbox": [10, 10, 90, 500]},
+            {"text": "", "translation": "", "bbox": [10, 10, 90, 500]},
+            {"text": "three values", "bbox": [10, 10, 90]},
+            {"text": "no box"},
+            {"text": "inverted", "bbox": [500, 500, 100, 100]},
+            {"text": "string box", "bbox": "10,10,90,500"}
+        ]"#;
+        let blocks = parse_ocr_blocks(reply).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].translation, "fine");
+    }
+
+    #[test]
+    fn ocr_blocks_clamp_to_the_capture() {
+        let blocks = parse_ocr_blocks(r#"[{"text": "edge", "bbox": [-20, 900, 1200, 1500]}]"#).unwrap();
+        assert_eq!((blocks[0].left, blocks[0].top, blocks[0].right, blocks[0].bottom), (0.9, 0.0, 1.0, 1.0));
+        // Entirely off the capture collapses to an empty box and is dropped
+        assert_eq!(parse_ocr_blocks(r#"[{"text": "gone", "bbox": [1100, 1100, 1200, 1200]}]"#), None);
+    }
+
+    #[test]
+    fn ocr_blocks_garbage_is_none() {
+        assert_eq!(parse_ocr_blocks("Sorry, I can't read this image."), None);
+        assert_eq!(parse_ocr_blocks("[not json]"), None);
+        assert_eq!(parse_ocr_blocks("] backwards ["), None);
+        assert_eq!(parse_ocr_blocks("[]"), None);
+        assert_eq!(parse_ocr_blocks(r#"[{"text": "cut off", "bbox": [1, 2"#), None);
+    }
+}
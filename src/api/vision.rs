@@ -1,29 +1,36 @@
 use anyhow::Result;
-use image::{ImageBuffer, Rgba};
 use base64::{Engine as _, engine::general_purpose};
-use std::io::{Cursor, BufRead, BufReader};
+use std::io::{BufRead, BufReader};
 use crate::APP;
+use crate::imaging::preprocess::PreparedImage;
+use super::schema::OutputSchema;
 use super::client::UREQ_AGENT;
 use super::types::{StreamChunk, ChatCompletionResponse};
 
+// Appended to the prompt of in-place presets; parsed by types::parse_ocr_blocks
+pub const OCR_BLOCKS_INSTRUCTION: &str = "\n\nReturn ONLY a JSON array, no markdown. Add one object per separate block of text in the image (a paragraph, label, button or speech bubble): {\"text\": the original text, \"translation\": the result your instructions ask for, \"bbox\": [ymin, xmin, ymax, xmax]} with coordinates normalized to 0-1000 of the image size.";
+
 pub fn translate_image_streaming<F>(
     groq_api_key: &str,
     gemini_api_key: &str,
     prompt: String,
     model: String,
     provider: String,
-    image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    image: &PreparedImage,
     streaming_enabled: bool,
-    use_json_format: bool,
+    schema: Option<&OutputSchema>,
     mut on_chunk: F,
 ) -> Result<String>
 where
     F: FnMut(&str),
 {
-    // Reverted resizing logic as it caused issues with main overlay capture
-    let mut image_data = Vec::new();
-    image.write_to(&mut Cursor::new(&mut image_data), image::ImageFormat::Png)?;
-    let b64_image = general_purpose::STANDARD.encode(&image_data);
+    // Already preprocessed/encoded per preset (imaging::preprocess)
+    let mime_type = image.mime_type();
+    let b64_image = general_purpose::STANDARD.encode(&image.data);
+    let prompt = match schema {
+        Some(schema) => format!("{}{}", prompt, schema.prompt_instruction()),
+        None => prompt,
+    };
 
     let mut full_content = String::new();
 
@@ -46,25 +53,31 @@ where
             )
         };
 
-        let payload = serde_json::json!({
+        let mut payload = serde_json::json!({
             "contents": [{
                 "role": "user",
                 "parts": [
                     { "text": prompt },
                     {
                         "inline_data": {
-                            "mime_type": "image/png",
+                            "mime_type": mime_type,
                             "data": b64_image
                         }
                     }
                 ]
             }]
         });
+        if let Some(schema) = schema {
+            payload["generationConfig"] = schema.gemini_generation_config();
+        }
 
         let resp = UREQ_AGENT.post(&url)
             .set("x-goog-api-key", gemini_api_key)
             .send_json(payload)
             .map_err(|e| {
+                if matches!(e, ureq::Error::Transport(_)) {
+                    return anyhow::anyhow!("NETWORK_ERROR");
+                }
                 let err_str = e.to_string();
                 if err_str.contains("401") || err_str.contains("403") {
                     anyhow::anyhow!("INVALID_API_KEY")
@@ -120,7 +133,7 @@ where
             return Err(anyhow::anyhow!("NO_API_KEY"));
         }
 
-        let payload = if streaming_enabled {
+        let mut payload = if streaming_enabled {
             serde_json::json!({
                 "model": model,
                 "messages": [
@@ -128,7 +141,7 @@ where
                         "role": "user",
                         "content": [
                             { "type": "text", "text": prompt },
-                            { "type": "image_url", "image_url": { "url": format!("data:image/png;base64,{}", b64_image) } }
+                            { "type": "image_url", "image_url": { "url": format!("data:{};base64,{}", mime_type, b64_image) } }
                         ]
                     }
                 ],
@@ -144,7 +157,7 @@ where
                         "role": "user",
                         "content": [
                             { "type": "text", "text": prompt },
-                            { "type": "image_url", "image_url": { "url": format!("data:image/png;base64,{}", b64_image) } }
+                            { "type": "image_url", "image_url": { "url": format!("data:{};base64,{}", mime_type, b64_image) } }
                         ]
                     }
                 ],
@@ -155,11 +168,17 @@ where
             
             payload_obj
         };
+        if let Some(schema) = schema {
+            payload["response_format"] = schema.groq_response_format();
+        }
 
         let resp = UREQ_AGENT.post("https://api.groq.com/openai/v1/chat/completions")
             .set("Authorization", &format!("Bearer {}", groq_api_key))
             .send_json(payload)
             .map_err(|e| {
+                if matches!(e, ureq::Error::Transport(_)) {
+                    return anyhow::anyhow!("NETWORK_ERROR");
+                }
                 let err_str = e.to_string();
                 if err_str.contains("401") {
                     anyhow::anyhow!("INVALID_API_KEY")
@@ -210,26 +229,15 @@ where
                 .map_err(|e| anyhow::anyhow!("Failed to parse non-streaming response: {}", e))?;
 
             if let Some(choice) = chat_resp.choices.first() {
-                let content_str = &choice.message.content;
-                
-                if use_json_format {
-                    if let Ok(json_obj) = serde_json::from_str::<serde_json::Value>(content_str) {
-                        if let Some(translation) = json_obj.get("translation").and_then(|v| v.as_str()) {
-                            full_content = translation.to_string();
-                        } else {
-                            full_content = content_str.clone();
-                        }
-                    } else {
-                        full_content = content_str.clone();
-                    }
-                } else {
-                    full_content = content_str.clone();
-                }
-                
+                full_content = choice.message.content.clone();
                 on_chunk(&full_content);
             }
         }
     }
 
+    if let Some(schema) = schema {
+        full_content = schema.extract(&full_content);
+    }
+
     Ok(full_content)
 }
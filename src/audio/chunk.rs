@@ -0,0 +1,138 @@
+// --- LONG RECORDING CHUNKING ---
+// Providers reject uploads past a size limit, so long recordings are cut at the
+// quietest point near each limit and the transcripts are joined back together.
+
+use std::ops::Range;
+use super::vad::{frame_rms, FRAME_MS};
+
+const MAX_OVERLAP_WORDS: usize = 40;
+
+// Splits mono samples into ranges of at most `max_chunk_secs`. Each cut lands on the
+// quietest frame of the last quarter of the chunk, or right at the limit when nothing
+// there is clearly quieter; the next chunk starts `overlap_secs` earlier so a word cut
+// in half is still heard whole by one of them.
+pub fn split_at_silence(samples: &[f32], sample_rate: u32, max_chunk_secs: u32, overlap_secs: u32) -> Vec<Range<usize>> {
+    let max_len = (sample_rate * max_chunk_secs) as usize;
+    if max_len == 0 || samples.len() <= max_len {
+        return vec![0..samples.len()];
+    }
+
+    let frame_len = ((sample_rate * FRAME_MS / 1000) as usize).max(1);
+    let overlap = (sample_rate * overlap_secs) as usize;
+    let search = max_len / 4;
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let limit = start + max_len;
+        if limit >= samples.len() {
+            chunks.push(start..samples.len());
+            break;
+        }
+
+        // A pause has to be well below the level at the limit to move the cut
+        let mut cut = limit;
+        let mut quietest = frame_rms(&samples[limit.saturating_sub(frame_len)..limit]) * 0.5;
+        let mut pos = limit - search;
+        while pos + frame_len <= limit {
+            let rms = frame_rms(&samples[pos..pos + frame_len]);
+            if rms < quietest {
+                quietest = rms;
+                cut = pos + frame_len / 2;
+            }
+            pos += frame_len;
+        }
+
+        chunks.push(start..cut);
+        start = cut.saturating_sub(overlap).max(start + 1);
+    }
+    chunks
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+// Joins chunk transcripts, dropping the words the overlap made both chunks hear.
+// The longest run of words ending one part and starting the next is removed once.
+pub fn stitch_transcripts(parts: &[String]) -> String {
+    let mut words: Vec<&str> = Vec::new();
+
+    for part in parts {
+        let next: Vec<&str> = part.split_whitespace().collect();
+        let max_k = MAX_OVERLAP_WORDS.min(words.len()).min(next.len());
+
+        let skip = (1..=max_k).rev().find(|&k| {
+            words[words.len() - k..].iter().zip(&next[..k])
+                .all(|(a, b)| normalize_word(a) == normalize_word(b))
+        }).unwrap_or(0);
+
+        words.extend_from_slice(&next[skip..]);
+    }
+
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn tone(secs: f32) -> Vec<f32> {
+        (0..(RATE as f32 * secs) as usize)
+            .map(|i| 0.3 * (2.0 * std::f32::consts::PI * 200.0 * i as f32 / RATE as f32).sin())
+            .collect()
+    }
+
+    fn at(secs: f32) -> usize {
+        (RATE as f32 * secs) as usize
+    }
+
+    #[test]
+    fn short_recording_is_one_chunk() {
+        assert_eq!(split_at_silence(&tone(5.0), RATE, 10, 1), vec![0..at(5.0)]);
+    }
+
+    #[test]
+    fn no_silence_splits_at_the_limit() {
+        let chunks = split_at_silence(&tone(25.0), RATE, 10, 1);
+        assert_eq!(chunks, vec![0..at(10.0), at(9.0)..at(19.0), at(18.0)..at(25.0)]);
+    }
+
+    #[test]
+    fn cuts_land_in_silence() {
+        let mut samples = tone(8.5);
+        samples.extend(vec![0.0; at(0.3)]); // 8.5 - 8.8 s
+        samples.extend(tone(8.0));
+        samples.extend(vec![0.0; at(0.4)]); // 16.8 - 17.2 s
+        samples.extend(tone(6.0));
+
+        let chunks = split_at_silence(&samples, RATE, 10, 1);
+        assert_eq!(chunks.len(), 3);
+        assert!((at(8.5)..at(8.8)).contains(&chunks[0].end), "first cut at {}", chunks[0].end);
+        assert_eq!(chunks[1].start, chunks[0].end - at(1.0));
+        assert!((at(16.8)..at(17.2)).contains(&chunks[1].end), "second cut at {}", chunks[1].end);
+        assert_eq!(chunks[2].end, samples.len());
+        assert!(chunks.iter().all(|c| c.len() <= at(10.0)));
+    }
+
+    #[test]
+    fn overlap_words_removed_once() {
+        let parts = vec![
+            "the quick brown fox jumps".to_string(),
+            "Fox jumps, over the lazy dog".to_string(),
+        ];
+        assert_eq!(stitch_transcripts(&parts), "the quick brown fox jumps over the lazy dog");
+
+        // Repeated words are only dropped for the overlap itself
+        let parts = vec!["we said hello hello".to_string(), "hello hello world".to_string()];
+        assert_eq!(stitch_transcripts(&parts), "we said hello hello world");
+    }
+
+    #[test]
+    fn no_overlap_joins_everything() {
+        let parts = vec!["first part".to_string(), "second part".to_string(), String::new(), "third".to_string()];
+        assert_eq!(stitch_transcripts(&parts), "first part second part third");
+    }
+}
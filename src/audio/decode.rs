@@ -0,0 +1,85 @@
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use super::error::AudioError;
+
+// --- AUDIO FILE DECODING ---
+// Existing recordings are decoded to interleaved f32 so they can take the same
+// resample/encode/transcribe path as captured audio.
+
+pub const SUPPORTED_EXTENSIONS: [&str; 4] = ["wav", "flac", "mp3", "ogg"];
+
+pub struct DecodedAudio {
+    pub samples: Vec<f32>, // Interleaved
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+pub fn is_supported_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+pub fn decode_audio_file(path: &Path) -> Result<DecodedAudio, AudioError> {
+    let decode_err = |e: Error| AudioError::Decode(e.to_string());
+    let file = std::fs::File::open(path).map_err(|e| AudioError::Decode(e.to_string()))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(decode_err)?;
+    let mut format = probed.format;
+
+    let track = format.default_track().ok_or_else(|| AudioError::Decode("no audio track".to_string()))?;
+    let track_id = track.id;
+    let codec_params = track.codec_params.clone();
+    let mut decoder = symphonia::default::get_codecs().make(&codec_params, &DecoderOptions::default())
+        .map_err(decode_err)?;
+
+    let mut sample_rate = codec_params.sample_rate.unwrap_or(0);
+    let mut channels = codec_params.channels.map(|c| c.count() as u16).unwrap_or(0);
+    let mut samples = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(p) => p,
+            // End of stream
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(Error::ResetRequired) => break,
+            Err(e) => return Err(decode_err(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count() as u16;
+                let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buf.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buf.samples());
+            },
+            // A damaged frame only loses that frame
+            Err(Error::DecodeError(e)) => eprintln!("Audio decode warning: {}", e),
+            Err(e) => return Err(decode_err(e)),
+        }
+    }
+
+    if samples.is_empty() || sample_rate == 0 || channels == 0 {
+        return Err(AudioError::EmptyRecording);
+    }
+    Ok(DecodedAudio { samples, sample_rate, channels })
+}
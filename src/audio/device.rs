@@ -0,0 +1,54 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+
+// --- CAPTURE DEVICES ---
+// Presets store devices by name (cpal has no stable id). A name that no longer
+// exists (unplugged headset, renamed driver) falls back to the system default.
+
+// Loopback capture of output devices needs WASAPI on Windows
+pub fn capture_host(loopback: bool) -> cpal::Host {
+    #[cfg(target_os = "windows")]
+    {
+        if loopback {
+            return cpal::host_from_id(cpal::HostId::Wasapi).unwrap_or(cpal::default_host());
+        }
+    }
+    let _ = loopback;
+    cpal::default_host()
+}
+
+pub fn input_device_names() -> Vec<String> {
+    capture_host(false).input_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+pub fn output_device_names() -> Vec<String> {
+    capture_host(true).output_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+// Empty name = default device
+pub fn input_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    if !name.is_empty() {
+        let found = host.input_devices().ok()
+            .and_then(|mut devices| devices.find(|d| d.name().is_ok_and(|n| n == name)));
+        if found.is_some() {
+            return found;
+        }
+        eprintln!("Input device '{}' not found, using default.", name);
+    }
+    host.default_input_device()
+}
+
+pub fn output_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    if !name.is_empty() {
+        let found = host.output_devices().ok()
+            .and_then(|mut devices| devices.find(|d| d.name().is_ok_and(|n| n == name)));
+        if found.is_some() {
+            return found;
+        }
+        eprintln!("Output device '{}' not found, using default.", name);
+    }
+    host.default_output_device()
+}
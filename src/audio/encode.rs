@@ -0,0 +1,116 @@
+use std::io::Cursor;
+use super::flac::encode_flac;
+use super::error::AudioError;
+
+// Container used for uploads and history files, chosen per preset
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+}
+
+impl AudioFormat {
+    pub fn from_preset(value: &str) -> Self {
+        match value {
+            "flac" => AudioFormat::Flac,
+            _ => AudioFormat::Wav,
+        }
+    }
+
+    // Recognizes already encoded data (history files, RefineContext::Audio)
+    pub fn detect(data: &[u8]) -> Self {
+        if data.starts_with(b"fLaC") { AudioFormat::Flac } else { AudioFormat::Wav }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Flac => "audio/flac",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+        }
+    }
+}
+
+// 16-bit PCM WAV in memory
+pub fn encode_wav(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>, AudioError> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let encode_err = |e: hound::Error| AudioError::Encode(e.to_string());
+
+    let mut wav_cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut wav_cursor, spec).map_err(encode_err)?;
+        for &sample in samples {
+            writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).map_err(encode_err)?;
+        }
+        writer.finalize().map_err(encode_err)?;
+    }
+    Ok(wav_cursor.into_inner())
+}
+
+pub fn encode(samples: &[f32], sample_rate: u32, channels: u16, format: AudioFormat) -> Result<Vec<u8>, AudioError> {
+    match format {
+        AudioFormat::Wav => encode_wav(samples, sample_rate, channels),
+        AudioFormat::Flac => Ok(encode_flac(samples, sample_rate, channels)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(data: &[u8], pos: usize) -> u16 {
+        u16::from_le_bytes([data[pos], data[pos + 1]])
+    }
+
+    fn u32_at(data: &[u8], pos: usize) -> u32 {
+        u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+    }
+
+    #[test]
+    fn wav_header_and_data() {
+        let samples = [0.0, 0.5, -0.5, 1.0, -1.0];
+        let wav = encode_wav(&samples, 16000, 1).unwrap();
+
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u16_at(&wav, 20), 1); // PCM
+        assert_eq!(u16_at(&wav, 22), 1); // Channels
+        assert_eq!(u32_at(&wav, 24), 16000);
+        assert_eq!(u32_at(&wav, 28), 32000); // Byte rate
+        assert_eq!(u16_at(&wav, 32), 2); // Block align
+        assert_eq!(u16_at(&wav, 34), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(&wav, 40), samples.len() as u32 * 2);
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+
+        let pcm: Vec<i16> = wav[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        assert_eq!(pcm, vec![0, 16383, -16383, i16::MAX, -i16::MAX]);
+    }
+
+    #[test]
+    fn wav_clamps_out_of_range_samples() {
+        let wav = encode_wav(&[1.5, -2.0, 100.0, f32::NEG_INFINITY], 48000, 2).unwrap();
+        assert_eq!(u16_at(&wav, 22), 2);
+        assert_eq!(u32_at(&wav, 28), 48000 * 4);
+        let pcm: Vec<i16> = wav[44..].chunks(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        assert_eq!(pcm, vec![i16::MAX, -i16::MAX, i16::MAX, -i16::MAX]);
+    }
+
+    #[test]
+    fn detects_encoded_format() {
+        assert_eq!(AudioFormat::detect(&encode_wav(&[0.0; 10], 16000, 1).unwrap()), AudioFormat::Wav);
+        assert_eq!(AudioFormat::detect(&encode(&[0.0; 10], 16000, 1, AudioFormat::Flac).unwrap()), AudioFormat::Flac);
+    }
+}
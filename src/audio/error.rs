@@ -0,0 +1,47 @@
+use std::fmt;
+
+// --- AUDIO ERRORS ---
+// Failures of the capture/encode path. `code()` is the key used by
+// overlay::utils::get_error_message for the localized text.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioError {
+    NoInputDevice,
+    NoOutputDevice,
+    Config(String),
+    Stream(String),
+    UnsupportedSampleFormat(String),
+    Encode(String),
+    Decode(String),
+    EmptyRecording,
+}
+
+impl AudioError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AudioError::NoInputDevice => "NO_INPUT_DEVICE",
+            AudioError::NoOutputDevice => "NO_OUTPUT_DEVICE",
+            AudioError::Config(_) => "AUDIO_CONFIG_FAILED",
+            AudioError::Stream(_) => "AUDIO_STREAM_FAILED",
+            AudioError::UnsupportedSampleFormat(_) => "UNSUPPORTED_SAMPLE_FORMAT",
+            AudioError::Encode(_) => "AUDIO_ENCODE_FAILED",
+            AudioError::Decode(_) => "AUDIO_DECODE_FAILED",
+            AudioError::EmptyRecording => "EMPTY_RECORDING",
+        }
+    }
+}
+
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::Config(detail)
+            | AudioError::Stream(detail)
+            | AudioError::UnsupportedSampleFormat(detail)
+            | AudioError::Encode(detail)
+            | AudioError::Decode(detail) => write!(f, "{}: {}", self.code(), detail),
+            _ => write!(f, "{}", self.code()),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
@@ -0,0 +1,325 @@
+// --- FLAC ENCODER ---
+// Minimal lossless encoder for 16-bit PCM: fixed linear predictors (order 0-4)
+// with a single Rice partition per subframe. Speech typically ends up at
+// 50-60% of the WAV size, which both Whisper and Gemini accept as audio/flac.
+
+const BLOCK_SIZE: usize = 4096;
+const MAX_RICE_PARAM: u32 = 14; // 15 is the escape code
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    nbits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self { bytes: Vec::new(), acc: 0, nbits: 0 }
+    }
+
+    // Writes the low `bits` bits of `value`, MSB first (bits <= 32)
+    fn write(&mut self, value: u64, bits: u32) {
+        if bits == 0 { return; }
+        self.acc = (self.acc << bits) | (value & ((1u64 << bits) - 1));
+        self.nbits += bits;
+        while self.nbits >= 8 {
+            self.nbits -= 8;
+            self.bytes.push((self.acc >> self.nbits) as u8);
+        }
+        self.acc &= (1u64 << self.nbits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i64, bits: u32) {
+        self.write(value as u64, bits);
+    }
+
+    fn write_unary(&mut self, zeros: u64) {
+        let mut left = zeros;
+        while left >= 32 {
+            self.write(0, 32);
+            left -= 32;
+        }
+        self.write(1, left as u32 + 1);
+    }
+
+    // Pads with zero bits up to the next byte boundary
+    fn align(&mut self) {
+        if self.nbits > 0 {
+            self.write(0, 8 - self.nbits);
+        }
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+// FLAC's extended UTF-8 style coding of the frame number
+fn write_utf8_number(w: &mut BitWriter, n: u64) {
+    if n < 0x80 {
+        w.write(n, 8);
+        return;
+    }
+    let mut len = 2;
+    while len < 7 && n >= 1u64 << (5 * len + 1) {
+        len += 1;
+    }
+    let lead = (0xFFu64 << (8 - len)) & 0xFF;
+    w.write(lead | (n >> (6 * (len - 1))), 8);
+    for i in (0..len - 1).rev() {
+        w.write(0x80 | ((n >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+fn fixed_residual(samples: &[i64], order: usize) -> Vec<i64> {
+    (order..samples.len()).map(|i| {
+        let s = samples;
+        match order {
+            0 => s[i],
+            1 => s[i] - s[i - 1],
+            2 => s[i] - 2 * s[i - 1] + s[i - 2],
+            3 => s[i] - 3 * s[i - 1] + 3 * s[i - 2] - s[i - 3],
+            _ => s[i] - 4 * s[i - 1] + 6 * s[i - 2] - 4 * s[i - 3] + s[i - 4],
+        }
+    }).collect()
+}
+
+fn zigzag(r: i64) -> u64 {
+    ((r << 1) ^ (r >> 63)) as u64
+}
+
+// Returns (rice parameter, total residual bits)
+fn best_rice_param(residual: &[i64]) -> (u32, u64) {
+    let folded: Vec<u64> = residual.iter().map(|&r| zigzag(r)).collect();
+    (0..=MAX_RICE_PARAM)
+        .map(|k| (k, folded.iter().map(|&u| (u >> k) + 1 + k as u64).sum::<u64>()))
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, u64::MAX))
+}
+
+fn write_subframe(w: &mut BitWriter, samples: &[i64], bps: u32) {
+    // Silent / constant blocks
+    if samples.iter().all(|&s| s == samples[0]) {
+        w.write(0, 1);
+        w.write(0b000000, 6);
+        w.write(0, 1);
+        w.write_signed(samples[0], bps);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * bps as u64;
+    let mut best: Option<(usize, u32, u64, Vec<i64>)> = None;
+    for order in 0..=4usize.min(samples.len() - 1) {
+        let residual = fixed_residual(samples, order);
+        let (k, bits) = best_rice_param(&residual);
+        let total = bits + order as u64 * bps as u64 + 10;
+        if best.as_ref().is_none_or(|b| total < b.2) {
+            best = Some((order, k, total, residual));
+        }
+    }
+
+    match best {
+        Some((order, k, total, residual)) if total < verbatim_bits => {
+            w.write(0, 1);
+            w.write(0b001000 | order as u64, 6);
+            w.write(0, 1);
+            for &s in &samples[..order] {
+                w.write_signed(s, bps);
+            }
+            w.write(0b00, 2); // Rice, 4-bit parameters
+            w.write(0, 4);    // Partition order 0
+            w.write(k as u64, 4);
+            for &r in &residual {
+                let u = zigzag(r);
+                w.write_unary(u >> k);
+                w.write(u, k);
+            }
+        },
+        _ => {
+            w.write(0, 1);
+            w.write(0b000001, 6);
+            w.write(0, 1);
+            for &s in samples {
+                w.write_signed(s, bps);
+            }
+        }
+    }
+}
+
+// Encodes interleaved f32 samples as a 16-bit FLAC stream
+pub fn encode_flac(samples: &[f32], sample_rate: u32, channels: u16) -> Vec<u8> {
+    let channels = channels.clamp(1, 8) as usize;
+    let bps = 16u32;
+    let pcm: Vec<i64> = samples.iter()
+        .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i64)
+        .collect();
+    let total_frames = pcm.len() / channels;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(b"fLaC");
+
+    // STREAMINFO (last metadata block)
+    let mut info = BitWriter::new();
+    info.write(1, 1);
+    info.write(0, 7);
+    info.write(34, 24);
+    info.write(BLOCK_SIZE as u64, 16);
+    info.write(BLOCK_SIZE as u64, 16);
+    info.write(0, 24); // Min/max frame size unknown
+    info.write(0, 24);
+    info.write(sample_rate as u64, 20);
+    info.write(channels as u64 - 1, 3);
+    info.write(bps as u64 - 1, 5);
+    info.write(total_frames as u64 >> 32, 4);
+    info.write(total_frames as u64 & 0xFFFF_FFFF, 32);
+    for _ in 0..4 {
+        info.write(0, 32); // MD5 not computed
+    }
+    out.extend(info.into_bytes());
+
+    for (frame_no, start) in (0..total_frames).step_by(BLOCK_SIZE).enumerate() {
+        let block = BLOCK_SIZE.min(total_frames - start);
+
+        let mut w = BitWriter::new();
+        w.write(0b11111111111110, 14);
+        w.write(0, 1);
+        w.write(0, 1);         // Fixed block size stream
+        w.write(0b0111, 4);    // Block size stored as 16 bits after the header
+        w.write(0b0000, 4);    // Sample rate from STREAMINFO
+        w.write(channels as u64 - 1, 4); // Independent channels
+        w.write(0b100, 3);     // 16 bits per sample
+        w.write(0, 1);
+        write_utf8_number(&mut w, frame_no as u64);
+        w.write(block as u64 - 1, 16);
+        let header = w.bytes.clone();
+        w.write(crc8(&header) as u64, 8);
+
+        for ch in 0..channels {
+            let channel: Vec<i64> = (start..start + block).map(|i| pcm[i * channels + ch]).collect();
+            write_subframe(&mut w, &channel, bps);
+        }
+
+        let mut frame = w.into_bytes();
+        let crc = crc16(&frame);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        out.extend(frame);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    // Decodes with symphonia, returns (interleaved samples, sample rate, channels)
+    fn decode(data: Vec<u8>) -> (Vec<i16>, u32, usize) {
+        let mss = MediaSourceStream::new(Box::new(Cursor::new(data)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("flac");
+        let mut format = symphonia::default::get_probe()
+            .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+            .expect("probe")
+            .format;
+        let track = format.default_track().expect("track");
+        let params = track.codec_params.clone();
+        let mut decoder = symphonia::default::get_codecs().make(&params, &DecoderOptions::default()).expect("decoder");
+
+        let mut samples = Vec::new();
+        while let Ok(packet) = format.next_packet() {
+            let decoded = decoder.decode(&packet).expect("decode");
+            let mut buf = SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+            buf.copy_interleaved_ref(decoded);
+            samples.extend_from_slice(buf.samples());
+        }
+        (samples, params.sample_rate.unwrap_or(0), params.channels.map_or(0, |c| c.count()))
+    }
+
+    fn pcm16(samples: &[f32]) -> Vec<i16> {
+        samples.iter().map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).collect()
+    }
+
+    fn assert_round_trip(samples: &[f32], sample_rate: u32, channels: u16) {
+        let (decoded, rate, decoded_channels) = decode(encode_flac(samples, sample_rate, channels));
+        assert_eq!(rate, sample_rate);
+        assert_eq!(decoded_channels, channels as usize);
+        assert_eq!(decoded.len(), samples.len());
+        assert!(decoded == pcm16(samples), "decoded samples differ");
+    }
+
+    fn speechlike(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len).map(|i| {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            let noise = (state >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+            0.4 * (i as f32 * 0.03).sin() + 0.2 * (i as f32 * 0.31).sin() + 0.05 * noise
+        }).collect()
+    }
+
+    #[test]
+    fn mono_round_trip() {
+        assert_round_trip(&speechlike(BLOCK_SIZE * 3, 1), 16000, 1);
+    }
+
+    #[test]
+    fn stereo_round_trip() {
+        let left = speechlike(BLOCK_SIZE * 2, 2);
+        let right = speechlike(BLOCK_SIZE * 2, 3);
+        let interleaved: Vec<f32> = left.iter().zip(&right).flat_map(|(&l, &r)| [l, r]).collect();
+        assert_round_trip(&interleaved, 48000, 2);
+    }
+
+    #[test]
+    fn partial_last_block() {
+        assert_round_trip(&speechlike(BLOCK_SIZE * 2 + 123, 4), 16000, 1);
+    }
+
+    #[test]
+    fn digital_silence() {
+        let silence = vec![0.0; BLOCK_SIZE + 500];
+        let encoded = encode_flac(&silence, 16000, 1);
+        // Constant subframes: a few bytes per block instead of 2 per sample
+        assert!(encoded.len() < 200, "{} bytes", encoded.len());
+        assert_round_trip(&silence, 16000, 1);
+    }
+
+    #[test]
+    fn full_scale() {
+        let mut samples: Vec<f32> = (0..BLOCK_SIZE).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        samples.extend([1.0; 300]);
+        samples.extend([-1.0; 300]);
+        // Out of range input is clamped the same way
+        samples.extend([1.5, -2.0, 0.0]);
+        assert_round_trip(&samples, 16000, 1);
+    }
+}
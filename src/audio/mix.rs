@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+use super::resample::{downmix_to_mono, StreamResampler};
+
+// --- MIC + SYSTEM AUDIO MIXING ---
+// Both captures arrive as separate streams with their own rate and channel count.
+// Each is brought to mono at the output rate and the two are summed.
+
+const MAX_LAG_MS: u32 = 200; // Loopback sends nothing while no sound plays, so it is padded with silence after this
+
+pub struct CaptureMixer {
+    rate: u32,
+    mic_channels: u16,
+    system_channels: u16,
+    mic_resampler: StreamResampler,
+    system_resampler: StreamResampler,
+    mic: VecDeque<f32>,
+    system: VecDeque<f32>,
+}
+
+impl CaptureMixer {
+    // Output is mono at `rate`
+    pub fn new(rate: u32, mic_format: (u32, u16), system_format: (u32, u16)) -> Self {
+        Self {
+            rate,
+            mic_channels: mic_format.1,
+            system_channels: system_format.1,
+            mic_resampler: StreamResampler::new(mic_format.0, rate),
+            system_resampler: StreamResampler::new(system_format.0, rate),
+            mic: VecDeque::new(),
+            system: VecDeque::new(),
+        }
+    }
+
+    pub fn push_mic(&mut self, samples: &[f32]) {
+        let mono = downmix_to_mono(samples, self.mic_channels);
+        self.mic.extend(self.mic_resampler.process(&mono));
+    }
+
+    pub fn push_system(&mut self, samples: &[f32]) {
+        let mono = downmix_to_mono(samples, self.system_channels);
+        self.system.extend(self.system_resampler.process(&mono));
+    }
+
+    // Returns the mixed samples both sides have delivered so far
+    pub fn drain(&mut self) -> Vec<f32> {
+        let max_lag = (self.rate * MAX_LAG_MS / 1000) as usize;
+        let longest = self.mic.len().max(self.system.len());
+        if longest > max_lag {
+            let target = longest - max_lag;
+            for side in [&mut self.mic, &mut self.system] {
+                while side.len() < target {
+                    side.push_back(0.0);
+                }
+            }
+        }
+
+        let n = self.mic.len().min(self.system.len());
+        self.mic.drain(..n)
+            .zip(self.system.drain(..n))
+            .map(|(a, b)| (a + b).clamp(-1.0, 1.0))
+            .collect()
+    }
+
+    // Everything left, padding the shorter side (end of recording)
+    pub fn flush(&mut self) -> Vec<f32> {
+        let n = self.mic.len().max(self.system.len());
+        self.mic.resize(n, 0.0);
+        self.system.resize(n, 0.0);
+        self.drain()
+    }
+}
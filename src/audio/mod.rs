@@ -0,0 +1,10 @@
+pub mod vad;
+pub mod resample;
+pub mod flac;
+pub mod encode;
+pub mod chunk;
+pub mod decode;
+pub mod device;
+pub mod mix;
+pub mod error;
+pub mod playback;
@@ -0,0 +1,43 @@
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::Media::Audio::{PlaySoundW, SND_MEMORY, SND_NODEFAULT, SND_SYNC};
+use std::path::PathBuf;
+use super::decode::decode_audio_file;
+use super::encode::encode_wav;
+
+// --- WAV PLAYBACK ---
+// In-memory WAV playback through PlaySound. Only one sound plays at a time;
+// starting a new one (or `stop_playback`) cuts the current one off.
+
+// Blocks until the sound finished or was stopped
+pub fn play_wav_blocking(wav: &[u8]) -> bool {
+    unsafe {
+        PlaySoundW(PCWSTR(wav.as_ptr() as *const u16), HMODULE(0), SND_MEMORY | SND_SYNC | SND_NODEFAULT).as_bool()
+    }
+}
+
+pub fn stop_playback() {
+    unsafe {
+        PlaySoundW(PCWSTR::null(), HMODULE(0), SND_NODEFAULT);
+    }
+}
+
+// Plays a saved recording (any decodable format) from `start_secs` on, on its own
+// thread. Used to jump to a transcript segment from the history.
+pub fn play_file_from(path: PathBuf, start_secs: f64) {
+    std::thread::spawn(move || {
+        let decoded = match decode_audio_file(&path) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                eprintln!("Playback error: {}", e);
+                return;
+            }
+        };
+        let frame = decoded.channels.max(1) as usize;
+        let skip = ((start_secs.max(0.0) * decoded.sample_rate as f64) as usize * frame).min(decoded.samples.len());
+        match encode_wav(&decoded.samples[skip..], decoded.sample_rate, decoded.channels) {
+            Ok(wav) => { play_wav_blocking(&wav); },
+            Err(e) => eprintln!("Playback error: {}", e),
+        }
+    });
+}
@@ -0,0 +1,196 @@
+// --- DOWNMIX / RESAMPLE ---
+// Speech APIs only need 16 kHz mono. Capture devices usually deliver 44.1/48 kHz
+// stereo, so uploads shrink ~6x after this stage.
+
+use std::collections::VecDeque;
+
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
+
+// Averages interleaved channels into one
+pub fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    if channels == 1 { return samples.to_vec(); }
+    samples.chunks(channels).map(|c| c.iter().sum::<f32>() / c.len() as f32).collect()
+}
+
+// Linear interpolation resampler. When downsampling, a moving average over one
+// source period is applied first to keep content above the new Nyquist from aliasing.
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() || from_rate == 0 || to_rate == 0 {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let filtered;
+    let source = if ratio > 1.0 {
+        filtered = moving_average(samples, ratio.ceil() as usize);
+        &filtered
+    } else {
+        samples
+    };
+
+    let out_len = ((samples.len() as f64) / ratio).floor() as usize;
+    let last = source.len() - 1;
+    (0..out_len).map(|i| {
+        let pos = i as f64 * ratio;
+        let idx = (pos.floor() as usize).min(last);
+        let frac = (pos - idx as f64) as f32;
+        let next = (idx + 1).min(last);
+        source[idx] + (source[next] - source[idx]) * frac
+    }).collect()
+}
+
+fn moving_average(samples: &[f32], window: usize) -> Vec<f32> {
+    if window <= 1 { return samples.to_vec(); }
+    let mut out = Vec::with_capacity(samples.len());
+    let mut sum = 0.0f32;
+    for i in 0..samples.len() {
+        sum += samples[i];
+        if i >= window { sum -= samples[i - window]; }
+        out.push(sum / (i + 1).min(window) as f32);
+    }
+    out
+}
+
+// Resampler for a stream that arrives in callback-sized pieces. Carries the
+// fractional read position and the filter/interpolation history across calls, so
+// consecutive chunks join without clicks and the output rate doesn't drift.
+pub struct StreamResampler {
+    ratio: f64,
+    window: usize,           // Moving average length (1 = no filtering)
+    recent: VecDeque<f32>,   // Last `window` raw samples
+    last: Option<f32>,       // Last filtered sample of the previous call
+    pos: f64,                // Next output position, relative to `last`
+}
+
+impl StreamResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        let ratio = if from_rate == 0 || to_rate == 0 { 1.0 } else { from_rate as f64 / to_rate as f64 };
+        let window = if ratio > 1.0 { ratio.ceil() as usize } else { 1 };
+        Self { ratio, window, recent: VecDeque::with_capacity(window + 1), last: None, pos: 0.0 }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        if self.ratio == 1.0 {
+            return samples.to_vec();
+        }
+
+        // Filtered source: the previous call's last sample, then this call's samples
+        let mut source = Vec::with_capacity(samples.len() + 1);
+        source.extend(self.last);
+        for &sample in samples {
+            self.recent.push_back(sample);
+            if self.recent.len() > self.window {
+                self.recent.pop_front();
+            }
+            source.push(self.recent.iter().sum::<f32>() / self.recent.len() as f32);
+        }
+        if source.is_empty() {
+            return Vec::new();
+        }
+
+        let last = source.len() - 1;
+        let mut out = Vec::with_capacity((samples.len() as f64 / self.ratio) as usize + 1);
+        while self.pos < last as f64 {
+            let idx = self.pos.floor() as usize;
+            let frac = (self.pos - idx as f64) as f32;
+            out.push(source[idx] + (source[idx + 1] - source[idx]) * frac);
+            self.pos += self.ratio;
+        }
+
+        self.pos -= last as f64;
+        self.last = Some(source[last]);
+        out
+    }
+}
+
+// Full stage used before upload: interleaved device audio -> 16 kHz mono
+pub fn to_speech_format(samples: &[f32], sample_rate: u32, channels: u16) -> Vec<f32> {
+    resample(&downmix_to_mono(samples, channels), sample_rate, TARGET_SAMPLE_RATE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(rate: u32, secs: f32, freq: f32, amplitude: f32) -> Vec<f32> {
+        (0..(rate as f32 * secs) as usize)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|x| x * x).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn stereo_48k_to_16k_mono_keeps_pitch_and_level() {
+        let mono = sine(48000, 1.0, 440.0, 0.5);
+        let stereo: Vec<f32> = mono.iter().flat_map(|&s| [s, s]).collect();
+
+        let out = to_speech_format(&stereo, 48000, 2);
+        assert_eq!(out.len(), 16000);
+
+        // One second of 440 Hz crosses zero 880 times
+        let crossings = out.windows(2).filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0)).count();
+        assert!((878..=882).contains(&crossings), "crossings = {}", crossings);
+
+        let expected = 0.5 / 2f32.sqrt();
+        assert!((rms(&out[100..]) - expected).abs() < expected * 0.02, "rms = {}", rms(&out));
+    }
+
+    #[test]
+    fn opposite_channels_cancel_to_silence() {
+        let left = sine(48000, 0.1, 440.0, 0.8);
+        let stereo: Vec<f32> = left.iter().flat_map(|&s| [s, -s]).collect();
+
+        let mono = downmix_to_mono(&stereo, 2);
+        assert_eq!(mono.len(), left.len());
+        assert!(mono.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn output_length_from_44k1() {
+        assert_eq!(resample(&vec![0.0; 44100], 44100, 16000).len(), 16000);
+        assert_eq!(resample(&vec![0.0; 4410], 44100, 16000).len(), 1600);
+        assert_eq!(resample(&vec![0.0; 441], 44100, 16000).len(), 160);
+    }
+
+    #[test]
+    fn stream_matches_one_shot_across_chunks() {
+        let signal = sine(48000, 1.0, 440.0, 0.5);
+        let whole = resample(&signal, 48000, 16000);
+
+        // Odd callback sizes, so chunk boundaries fall between output samples
+        let mut stream = StreamResampler::new(48000, 16000);
+        let mut chunked = Vec::new();
+        for chunk in signal.chunks(441) {
+            chunked.extend(stream.process(chunk));
+        }
+
+        // No sample lost per chunk: the streams stay in step over the whole second
+        assert!(whole.len().abs_diff(chunked.len()) <= 1, "{} vs {}", whole.len(), chunked.len());
+        for (a, b) in whole.iter().zip(&chunked) {
+            assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn stream_length_from_44k1() {
+        // 1 s in 10 ms callbacks
+        let mut stream = StreamResampler::new(44100, 16000);
+        let produced: usize = (0..100).map(|_| stream.process(&[0.0; 441]).len()).sum();
+        assert!((15999..=16000).contains(&produced), "{}", produced);
+
+        // 100-sample callbacks: rounding down per chunk would give 3600 instead of ~3628
+        let mut stream = StreamResampler::new(44100, 16000);
+        let produced: usize = (0..100).map(|_| stream.process(&[0.0; 100]).len()).sum();
+        assert!(produced.abs_diff(10000 * 16000 / 44100) <= 1, "{}", produced);
+    }
+
+    #[test]
+    fn same_rate_is_untouched() {
+        let samples = sine(16000, 0.05, 300.0, 0.3);
+        assert_eq!(resample(&samples, 16000, 16000), samples);
+    }
+}
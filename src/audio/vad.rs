@@ -0,0 +1,254 @@
+// --- VOICE ACTIVITY DETECTION ---
+// Frame based detector using short-time energy and zero-crossing rate.
+// Works on interleaved f32 samples straight from the capture callback.
+
+use std::ops::Range;
+use super::resample::downmix_to_mono;
+
+pub const FRAME_MS: u32 = 20;
+const MIN_ENERGY: f32 = 0.01;   // RMS below this is always silence (~ -40 dBFS)
+const NOISE_RATIO: f32 = 3.0;   // Speech must be this much louder than the noise floor
+const MAX_ZCR: f32 = 0.35;      // Higher crossing rates are hiss/fan noise rather than voice
+const MIN_SPEECH_MS: u32 = 100; // Shorter bursts (clicks, key presses) don't count as speech
+const CALIBRATION_MS: u32 = 300; // Opening stretch that only measures the background
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VadState {
+    Waiting, // No speech heard yet
+    Speech,
+    Silence, // Speech was heard, currently quiet
+}
+
+pub fn frame_rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() { return 0.0; }
+    (frame.iter().map(|x| x * x).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+pub fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 { return 0.0; }
+    let crossings = frame.windows(2).filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0)).count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+fn is_speech(rms: f32, zcr: f32, noise_floor: f32) -> bool {
+    rms > MIN_ENERGY.max(noise_floor * NOISE_RATIO) && zcr < MAX_ZCR
+}
+
+pub struct Vad {
+    channels: u16,
+    frame_len: usize,  // Mono samples per frame
+    pending: Vec<f32>, // Mono samples not yet forming a full frame
+    noise_floor: f32,
+    calibration_ms: u32, // Audio measured so far while calibrating
+    speech_ms: u32,
+    silence_ms: u32,
+    heard_speech: bool,
+}
+
+impl Vad {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            channels,
+            frame_len: ((sample_rate * FRAME_MS / 1000) as usize).max(1),
+            pending: Vec::new(),
+            noise_floor: f32::MAX,
+            calibration_ms: 0,
+            speech_ms: 0,
+            silence_ms: 0,
+            heard_speech: false,
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> VadState {
+        self.pending.extend(downmix_to_mono(samples, self.channels));
+
+        while self.pending.len() >= self.frame_len {
+            let frame: Vec<f32> = self.pending.drain(..self.frame_len).collect();
+            let rms = frame_rms(&frame);
+
+            // The quietest frame of the opening stretch seeds the floor, so a steady
+            // fan or music bed playing from the start never reads as speech
+            if self.calibration_ms < CALIBRATION_MS {
+                self.calibration_ms += FRAME_MS;
+                self.noise_floor = self.noise_floor.min(rms);
+                continue;
+            }
+
+            if is_speech(rms, zero_crossing_rate(&frame), self.noise_floor) {
+                self.speech_ms += FRAME_MS;
+                if self.speech_ms >= MIN_SPEECH_MS {
+                    self.heard_speech = true;
+                    self.silence_ms = 0;
+                }
+                // Creep up even during "speech": background noise that starts later
+                // (~4 s of it) ends up under the floor instead of holding off auto-stop
+                self.noise_floor = self.noise_floor * 0.998 + rms * 0.002;
+            } else {
+                self.speech_ms = 0;
+                if self.heard_speech {
+                    self.silence_ms += FRAME_MS;
+                }
+                // Track background noise slowly so a noisy room doesn't count as speech
+                self.noise_floor = self.noise_floor * 0.95 + rms * 0.05;
+            }
+        }
+
+        self.state()
+    }
+
+    pub fn state(&self) -> VadState {
+        if !self.heard_speech {
+            VadState::Waiting
+        } else if self.silence_ms == 0 {
+            VadState::Speech
+        } else {
+            VadState::Silence
+        }
+    }
+
+    pub fn silence_ms(&self) -> u32 {
+        self.silence_ms
+    }
+
+    // Starts listening for the next utterance, keeping the learned noise floor
+    pub fn reset(&mut self) {
+        self.speech_ms = 0;
+        self.silence_ms = 0;
+        self.heard_speech = false;
+    }
+}
+
+// Range of a recording without the silence at both ends, keeping `padding_ms`
+// around the speech. The whole input if no speech is found.
+pub fn trim_silence(samples: &[f32], sample_rate: u32, channels: u16, padding_ms: u32) -> Range<usize> {
+    let frame_len = ((sample_rate * FRAME_MS / 1000) as usize).max(1);
+    let mono = downmix_to_mono(samples, channels);
+    let channels = channels.max(1) as usize;
+
+    let frames: Vec<(f32, f32)> = mono.chunks(frame_len)
+        .map(|f| (frame_rms(f), zero_crossing_rate(f)))
+        .collect();
+    if frames.is_empty() { return 0..samples.len(); }
+
+    // Quietest 10% of the recording approximates the background level
+    let mut sorted: Vec<f32> = frames.iter().map(|f| f.0).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let noise_floor = sorted[sorted.len() / 10];
+
+    let speech = |f: &(f32, f32)| is_speech(f.0, f.1, noise_floor);
+    let (first, last) = match (frames.iter().position(speech), frames.iter().rposition(speech)) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return 0..samples.len(),
+    };
+
+    let pad = (padding_ms / FRAME_MS) as usize;
+    let start = first.saturating_sub(pad) * frame_len * channels;
+    let end = ((last + 1 + pad) * frame_len * channels).min(samples.len());
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn silence(ms: u32) -> Vec<f32> {
+        vec![0.0; (RATE * ms / 1000) as usize]
+    }
+
+    fn tone(ms: u32, freq: f32, amplitude: f32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * freq * i as f32 / RATE as f32).sin())
+            .collect()
+    }
+
+    // Deterministic white noise in -amplitude..amplitude
+    fn noise(ms: u32, amplitude: f32) -> Vec<f32> {
+        let mut seed: u32 = 12345;
+        (0..RATE * ms / 1000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+                amplitude * ((seed >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    fn run(parts: &[Vec<f32>]) -> Vad {
+        let mut vad = Vad::new(RATE, 1);
+        for part in parts {
+            // Callback-sized pieces, not aligned to frames
+            for chunk in part.chunks(441) {
+                vad.process(chunk);
+            }
+        }
+        vad
+    }
+
+    #[test]
+    fn silence_is_never_speech() {
+        assert_eq!(run(&[silence(2000)]).state(), VadState::Waiting);
+    }
+
+    #[test]
+    fn tone_burst_then_silence() {
+        let vad = run(&[silence(500), tone(400, 200.0, 0.3)]);
+        assert_eq!(vad.state(), VadState::Speech);
+
+        let vad = run(&[silence(500), tone(400, 200.0, 0.3), silence(600)]);
+        assert_eq!(vad.state(), VadState::Silence);
+        assert!(vad.silence_ms() >= 580, "silence_ms = {}", vad.silence_ms());
+    }
+
+    #[test]
+    fn short_click_is_not_speech() {
+        assert_eq!(run(&[silence(500), tone(40, 200.0, 0.5), silence(500)]).state(), VadState::Waiting);
+    }
+
+    #[test]
+    fn reset_keeps_the_noise_floor() {
+        let mut vad = run(&[tone(1000, 100.0, 0.05), tone(500, 200.0, 0.5), tone(600, 100.0, 0.05)]);
+        vad.reset();
+        assert_eq!(vad.state(), VadState::Waiting);
+        // Same hum right after the reset is still background
+        vad.process(&tone(1000, 100.0, 0.05));
+        assert_eq!(vad.state(), VadState::Waiting);
+    }
+
+    #[test]
+    fn white_noise_is_not_speech() {
+        assert_eq!(run(&[noise(2000, 0.3)]).state(), VadState::Waiting);
+    }
+
+    #[test]
+    fn steady_background_from_the_start_still_stops() {
+        // Low hum above MIN_ENERGY the whole time, one louder burst in the middle
+        let vad = run(&[tone(1000, 100.0, 0.05), tone(500, 200.0, 0.5), tone(1000, 100.0, 0.05)]);
+        assert_eq!(vad.state(), VadState::Silence);
+        assert!(vad.silence_ms() >= 900, "silence_ms = {}", vad.silence_ms());
+    }
+
+    #[test]
+    fn background_starting_after_speech_still_stops() {
+        // Floor was seeded from silence; the hum only starts once speech ended
+        let vad = run(&[silence(500), tone(500, 200.0, 0.5), tone(8000, 100.0, 0.05)]);
+        assert_eq!(vad.state(), VadState::Silence);
+    }
+
+    #[test]
+    fn trim_returns_the_speech_range() {
+        let samples = [silence(1000), tone(500, 200.0, 0.3), silence(1000)].concat();
+        let range = trim_silence(&samples, RATE, 1, 0);
+        let frame = (RATE * FRAME_MS / 1000) as usize;
+        assert!(range.start.abs_diff(RATE as usize) <= frame, "start = {}", range.start);
+        assert!(range.end.abs_diff(RATE as usize * 3 / 2) <= frame, "end = {}", range.end);
+
+        // Padding is kept around the speech, in samples of every channel
+        let stereo: Vec<f32> = samples.iter().flat_map(|s| [*s, *s]).collect();
+        let padded = trim_silence(&stereo, RATE, 2, 200);
+        assert!(padded.start.abs_diff(2 * (RATE as usize * 4 / 5)) <= 2 * frame, "start = {}", padded.start);
+
+        // No speech: nothing is cut
+        assert_eq!(trim_silence(&silence(500), RATE, 1, 0), 0..RATE as usize / 2);
+    }
+}
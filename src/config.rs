@@ -22,6 +22,25 @@ fn get_system_ui_language() -> String {
     }
 }
 
+// One key of a preset's structured output (JSON schema) option
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct OutputField {
+    pub name: String,
+    #[serde(default)]
+    pub description: String, // Tells the model what to put there
+}
+
+// A capture area remembered by a preset. Relative to its monitor so it
+// still matches after the monitor layout changes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CaptureRegion {
+    pub monitor: String, // Device name, e.g. \\.\DISPLAY1
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Hotkey {
     pub code: u32,
@@ -57,9 +76,83 @@ pub struct Preset {
     
     // --- Audio Fields ---
     #[serde(default = "default_audio_source")]
-    pub audio_source: String, // "mic" or "device"
+    pub audio_source: String, // "mic", "device" or "mix" (mic + system audio)
+    #[serde(default)]
+    pub audio_input_device: String, // Device name, empty = system default
+    #[serde(default)]
+    pub audio_output_device: String, // Loopback device name, empty = system default
     #[serde(default)]
     pub hide_recording_ui: bool,
+    #[serde(default)]
+    pub auto_stop_silence_secs: f32, // Stop recording after this much silence, 0 = manual stop only
+    #[serde(default)]
+    pub keep_original_audio: bool, // Store device-quality audio in history instead of the 16 kHz upload
+    #[serde(default = "default_audio_format")]
+    pub audio_format: String, // "wav" or "flac"
+    #[serde(default)]
+    pub live_transcribe: bool, // Transcribe utterances while still recording
+
+    // --- Whisper (Groq) Options ---
+    #[serde(default)]
+    pub whisper_language: String, // ISO-639-1 code, empty = auto-detect
+    #[serde(default)]
+    pub whisper_prompt: String, // Vocabulary / spelling hints
+    #[serde(default)]
+    pub whisper_temperature: f32,
+    #[serde(default, alias = "whisper_timestamps")]
+    pub timestamps: bool, // Segment timestamps: verbose_json for Whisper, [mm:ss] lines for Gemini
+    #[serde(default)]
+    pub diarization: bool, // Gemini only: speaker-labelled JSON transcript
+    #[serde(default = "default_subtitle_format")]
+    pub subtitle_format: String, // "none", "srt" or "vtt": written to Downloads after each timestamped transcription
+
+    // --- Image Preprocessing (applied before upload) ---
+    #[serde(default)]
+    pub image_max_dimension: u32, // Downscale longer side to this, 0 = full resolution
+    #[serde(default)]
+    pub image_upscale_below: u32, // Enlarge crops whose shorter side is below this (tiny text), 0 = off
+    #[serde(default)]
+    pub image_grayscale: bool,
+    #[serde(default)]
+    pub image_threshold: u8, // Black/white binarization cut-off, 0 = off
+    #[serde(default)]
+    pub image_sharpen: bool,
+    #[serde(default = "default_image_format")]
+    pub image_format: String, // "png", "jpeg" or "webp" (lossless)
+    #[serde(default = "default_image_quality")]
+    pub image_quality: u8, // JPEG quality 1-100
+    #[serde(default)]
+    pub ocr_language: String, // Local OCR models: BCP-47 tag, empty = Windows display languages
+    #[serde(default)]
+    pub local_ocr_fallback: bool, // Cloud models: extract the text offline when there is no API key or no connection
+    #[serde(default)]
+    pub overlay_blocks: bool, // In-place mode: model returns positioned JSON blocks drawn over the capture
+    #[serde(default = "default_cache_results")]
+    pub cache_results: bool, // Reuse the stored answer for an identical capture/recording + prompt + model
+    #[serde(default = "default_capture_mode")]
+    pub capture_mode: String, // "select" (drag), "last", "fixed", "active_window", "cursor_window" or "monitor:DeviceName"
+    #[serde(default)]
+    pub last_region: Option<CaptureRegion>, // Updated by every drag selection
+    #[serde(default)]
+    pub fixed_region: Option<CaptureRegion>,
+    #[serde(default)]
+    pub watch_mode: bool, // Keep sampling the region and re-send when it changes
+    #[serde(default = "default_watch_interval_ms")]
+    pub watch_interval_ms: u32,
+    #[serde(default = "default_watch_threshold")]
+    pub watch_threshold: f32, // Percent of changed pixels that counts as new content
+    #[serde(default)]
+    pub scroll_capture: bool, // Scroll the region and stitch the frames into one tall image
+    #[serde(default = "default_scroll_max_frames")]
+    pub scroll_max_frames: u32,
+
+    // --- Structured Output (JSON schema) ---
+    #[serde(default)]
+    pub structured_output: bool,
+    #[serde(default)]
+    pub output_fields: Vec<OutputField>, // Keys the model must return
+    #[serde(default)]
+    pub output_display_field: String, // Key shown/copied, empty = first field
 
     // --- Video Fields ---
     #[serde(default)]
@@ -71,9 +164,18 @@ pub struct Preset {
 
 fn default_preset_type() -> String { "image".to_string() }
 fn default_audio_source() -> String { "mic".to_string() }
+fn default_audio_format() -> String { "wav".to_string() }
+fn default_subtitle_format() -> String { "none".to_string() }
+fn default_image_format() -> String { "png".to_string() }
+fn default_image_quality() -> u8 { 85 }
 fn default_prompt_mode() -> String { "fixed".to_string() }
 fn default_theme_mode() -> ThemeMode { ThemeMode::System }
 fn default_auto_paste_newline() -> bool { true }
+fn default_cache_results() -> bool { true }
+fn default_capture_mode() -> String { "select".to_string() }
+fn default_watch_interval_ms() -> u32 { 800 }
+fn default_watch_threshold() -> f32 { 1.0 }
+fn default_scroll_max_frames() -> u32 { 20 }
 
 impl Default for Preset {
     fn default() -> Self {
@@ -99,11 +201,56 @@ impl Default for Preset {
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         }
     }
 }
 
+impl Preset {
+    // Prompt with the {key} language variables and {language} filled in
+    pub fn expanded_prompt(&self) -> String {
+        let mut prompt = self.prompt.clone();
+        for (key, value) in &self.language_vars {
+            prompt = prompt.replace(&format!("{{{}}}", key), value);
+        }
+        prompt.replace("{language}", &self.selected_language)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Config {
     pub api_key: String,
@@ -122,9 +269,32 @@ pub struct Config {
     #[serde(default)]
     pub run_as_admin_on_startup: bool, 
     // ------------------
+
+    // Text-to-speech: "gemini", "openai" (any OpenAI-compatible endpoint) or "local"
+    #[serde(default = "default_tts_provider")]
+    pub tts_provider: String,
+    #[serde(default)]
+    pub tts_voice: String, // Empty = provider default
+    #[serde(default = "default_tts_openai_url")]
+    pub tts_openai_url: String,
+    #[serde(default)]
+    pub tts_openai_api_key: String,
+    #[serde(default = "default_tts_openai_model")]
+    pub tts_openai_model: String,
+
+    // Result cache (see result_cache.rs)
+    #[serde(default = "default_result_cache_ttl_hours")]
+    pub result_cache_ttl_hours: u32, // 0 = never expires
+    #[serde(default = "default_result_cache_max_entries")]
+    pub result_cache_max_entries: usize, // 0 = cache disabled
 }
 
 fn default_history_limit() -> usize { 100 }
+fn default_tts_provider() -> String { "gemini".to_string() }
+fn default_tts_openai_url() -> String { "https://api.openai.com/v1".to_string() }
+fn default_tts_openai_model() -> String { "tts-1".to_string() }
+fn default_result_cache_ttl_hours() -> u32 { 24 }
+fn default_result_cache_max_entries() -> usize { 200 }
 
     impl Default for Config {
     fn default() -> Self {
@@ -161,6 +331,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -187,6 +391,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -216,6 +454,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -242,6 +514,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -268,6 +574,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -297,6 +637,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -326,6 +700,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -355,6 +763,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -381,6 +823,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -407,6 +883,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "device".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -433,6 +943,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -462,6 +1006,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "mic".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: false,
         };
 
@@ -488,6 +1066,40 @@ fn default_history_limit() -> usize { 100 }
             audio_source: "".to_string(),
             hide_recording_ui: false,
             video_capture_method: "region".to_string(),
+            auto_stop_silence_secs: 0.0,
+            keep_original_audio: false,
+            audio_format: "wav".to_string(),
+            live_transcribe: false,
+            whisper_language: String::new(),
+            whisper_prompt: String::new(),
+            whisper_temperature: 0.0,
+            timestamps: false,
+            subtitle_format: "none".to_string(),
+            audio_input_device: String::new(),
+            audio_output_device: String::new(),
+            diarization: false,
+            image_max_dimension: 0,
+            image_upscale_below: 0,
+            image_grayscale: false,
+            image_threshold: 0,
+            image_sharpen: false,
+            image_format: "png".to_string(),
+            image_quality: 85,
+            overlay_blocks: false,
+            structured_output: false,
+            output_fields: Vec::new(),
+            output_display_field: String::new(),
+            ocr_language: String::new(),
+            cache_results: true,
+            capture_mode: "select".to_string(),
+            last_region: None,
+            fixed_region: None,
+            watch_mode: false,
+            watch_interval_ms: 800,
+            watch_threshold: 1.0,
+            scroll_capture: false,
+            scroll_max_frames: 20,
+            local_ocr_fallback: false,
             is_upcoming: true, // Mark as upcoming to gray out in sidebar
         };
 
@@ -508,6 +1120,13 @@ fn default_history_limit() -> usize { 100 }
             start_in_tray: false,
             run_as_admin_on_startup: false,
             // --------------------
+            tts_provider: default_tts_provider(),
+            tts_voice: String::new(),
+            tts_openai_url: default_tts_openai_url(),
+            tts_openai_api_key: String::new(),
+            tts_openai_model: default_tts_openai_model(),
+            result_cache_ttl_hours: default_result_cache_ttl_hours(),
+            result_cache_max_entries: default_result_cache_max_entries(),
         }
     }
 }
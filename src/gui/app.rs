@@ -24,6 +24,22 @@ use crate::icon_gen;
 
 
 
+// Runs an audio file through the preferred preset if it is an audio preset, else the first audio preset
+fn transcribe_file_with_audio_preset(path: std::path::PathBuf, preferred_idx: Option<usize>) {
+    let preset = {
+        let app = crate::APP.lock().unwrap();
+        let presets = &app.config.presets;
+        preferred_idx.and_then(|i| presets.get(i))
+            .filter(|p| p.preset_type == "audio")
+            .or_else(|| presets.iter().find(|p| p.preset_type == "audio" && !p.is_upcoming))
+            .cloned()
+    };
+    match preset {
+        Some(preset) => crate::api::audio::transcribe_audio_file(preset, path),
+        None => eprintln!("No audio preset available to transcribe {}", path.display()),
+    }
+}
+
 lazy_static::lazy_static! {
     static ref RESTORE_SIGNAL: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
 }
@@ -63,6 +79,7 @@ pub struct SettingsApp {
     startup_stage: u8, 
     
     cached_monitors: Vec<String>,
+    cached_audio_devices: (Vec<String>, Vec<String>),
     
     updater: Option<Updater>,
     update_rx: Receiver<UpdateStatus>,
@@ -189,6 +206,14 @@ impl SettingsApp {
                         let _ = tx_menu.send(UserEvent::Menu(event.clone()));
                         ctx_menu.request_repaint();
                     }
+                    "1003" => {
+                        std::thread::spawn(|| {
+                            if let Some(path) = crate::gui::utils::pick_audio_file() {
+                                let active_idx = crate::APP.lock().unwrap().config.active_preset_idx;
+                                transcribe_file_with_audio_preset(path, Some(active_idx));
+                            }
+                        });
+                    }
                     _ => { let _ = tx_menu.send(UserEvent::Menu(event)); ctx_menu.request_repaint(); }
                 }
             }
@@ -201,6 +226,7 @@ impl SettingsApp {
         };
         
         let cached_monitors = get_monitor_names();
+        let cached_audio_devices = (crate::audio::device::input_device_names(), crate::audio::device::output_device_names());
         let (up_tx, up_rx) = channel();
         
         // Check for current admin state
@@ -241,6 +267,7 @@ impl SettingsApp {
             fade_in_start: None,
             startup_stage: 0,
             cached_monitors,
+            cached_audio_devices,
             updater: Some(Updater::new(up_tx)),
             update_rx: up_rx,
             update_status: UpdateStatus::Idle,
@@ -260,6 +287,12 @@ impl SettingsApp {
         }
 
         let mut state = self.app_state_ref.lock().unwrap();
+        // The selection overlay records last regions on the shared config; keep them
+        for preset in self.config.presets.iter_mut() {
+            if let Some(shared) = state.config.presets.iter().find(|p| p.id == preset.id) {
+                preset.last_region = shared.last_region.clone();
+            }
+        }
         state.hotkeys_updated = true;
         state.config = self.config.clone();
         drop(state);
@@ -506,6 +539,21 @@ impl eframe::App for SettingsApp {
             }
         }
 
+        // Audio files dropped on the window go through the selected (or first) audio preset
+        let dropped: Vec<std::path::PathBuf> = ctx.input(|i| {
+            i.raw.dropped_files.iter().filter_map(|f| f.path.clone()).collect()
+        });
+        for path in dropped {
+            if !crate::audio::decode::is_supported_file(&path) {
+                continue;
+            }
+            let preferred_idx = match self.view_mode {
+                ViewMode::Preset(idx) => Some(idx),
+                _ => None,
+            };
+            std::thread::spawn(move || transcribe_file_with_audio_preset(path, preferred_idx));
+        }
+
         if ctx.input(|i| i.viewport().close_requested()) {
             if !self.is_quitting {
                 ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
@@ -600,6 +648,7 @@ impl eframe::App for SettingsApp {
                                  idx, 
                                  &mut self.search_query, 
                                  &mut self.cached_monitors, 
+                                 &mut self.cached_audio_devices,
                                  &mut self.recording_hotkey_for_preset, 
                                  &self.hotkey_conflict_msg, 
                                  &text
@@ -6,7 +6,19 @@ pub struct LocaleText {
      pub history_empty: &'static str,
      pub clear_all_history_btn: &'static str,
      pub view_image_btn: &'static str, // NEW
+     pub view_processed_image_btn: &'static str,
+     pub view_processed_image_tooltip: &'static str,
      pub listen_audio_btn: &'static str, // NEW
+     pub rerun_btn: &'static str,
+     pub rerun_preset_model: &'static str,
+     pub rerun_original_label: &'static str,
+     pub timestamps_label: &'static str,
+     pub play_from_here_tooltip: &'static str,
+     pub stop_playback_btn: &'static str,
+     pub continue_chat_btn: &'static str,
+     pub conversation_label: &'static str,
+     pub chat_you_label: &'static str,
+     pub chat_ai_label: &'static str,
      pub prompt_mode_label: &'static str,
      pub prompt_mode_fixed: &'static str,
      pub prompt_mode_dynamic: &'static str,
@@ -15,6 +27,47 @@ pub struct LocaleText {
      pub get_key_link: &'static str,
      pub gemini_api_key_label: &'static str,
      pub gemini_get_key_link: &'static str,
+     pub tts_section: &'static str,
+     pub tts_provider_label: &'static str,
+     pub tts_provider_gemini: &'static str,
+     pub tts_provider_openai: &'static str,
+     pub tts_provider_local: &'static str,
+     pub tts_voice_label: &'static str,
+     pub tts_voice_hint: &'static str,
+     pub tts_openai_url_label: &'static str,
+     pub tts_openai_key_label: &'static str,
+     pub tts_openai_model_label: &'static str,
+     pub tts_local_note: &'static str,
+     pub result_cache_section: &'static str,
+     pub result_cache_ttl_label: &'static str,
+     pub result_cache_ttl_tooltip: &'static str,
+     pub result_cache_max_label: &'static str,
+     pub result_cache_max_tooltip: &'static str,
+     pub result_cache_entries_label: &'static str,
+     pub result_cache_clear_btn: &'static str,
+     pub cache_results_label: &'static str,
+     pub cache_results_tooltip: &'static str,
+     pub capture_mode_label: &'static str,
+     pub capture_mode_select: &'static str,
+     pub capture_mode_last: &'static str,
+     pub capture_mode_fixed: &'static str,
+     pub capture_mode_active_window: &'static str,
+     pub capture_mode_cursor_window: &'static str,
+     pub capture_mode_monitor: &'static str,
+     pub multi_region_hint: &'static str,
+     pub last_region_reselect_hint: &'static str,
+     pub capture_mode_tooltip: &'static str,
+     pub fixed_region_none: &'static str,
+     pub fixed_region_from_last_btn: &'static str,
+     pub fixed_region_from_last_tooltip: &'static str,
+     pub watch_mode_label: &'static str,
+     pub watch_mode_tooltip: &'static str,
+     pub watch_interval_label: &'static str,
+     pub watch_threshold_label: &'static str,
+     pub watch_threshold_tooltip: &'static str,
+     pub scroll_capture_label: &'static str,
+     pub scroll_capture_tooltip: &'static str,
+     pub scroll_max_frames_label: &'static str,
      pub presets_section: &'static str,
      pub global_settings: &'static str,
      pub preset_name_label: &'static str,
@@ -48,7 +101,56 @@ pub struct LocaleText {
      pub audio_source_label: &'static str,
      pub audio_src_mic: &'static str,
      pub audio_src_device: &'static str,
+     pub audio_src_mix: &'static str,
+     pub audio_src_mix_tooltip: &'static str,
+     pub audio_input_device_label: &'static str,
+     pub audio_output_device_label: &'static str,
+     pub audio_device_default: &'static str,
+     pub audio_device_missing: &'static str,
+     pub refresh_devices_btn: &'static str,
      pub hide_recording_ui_label: &'static str,
+     pub auto_stop_label: &'static str,
+     pub auto_stop_silence_label: &'static str,
+     pub keep_original_audio_label: &'static str,
+     pub audio_format_label: &'static str,
+     pub audio_format_flac: &'static str,
+     pub live_transcribe_label: &'static str,
+     pub live_transcribe_tooltip: &'static str,
+     pub transcribe_file_btn: &'static str,
+     pub transcribe_file_tooltip: &'static str,
+     pub whisper_section: &'static str,
+     pub whisper_language_label: &'static str,
+     pub whisper_temperature_label: &'static str,
+     pub whisper_prompt_label: &'static str,
+     pub whisper_prompt_hint: &'static str,
+     pub segment_timestamps_label: &'static str,
+     pub diarization_label: &'static str,
+     pub diarization_tooltip: &'static str,
+     pub overlay_blocks_label: &'static str,
+     pub overlay_blocks_tooltip: &'static str,
+     pub ocr_language_label: &'static str,
+     pub ocr_language_hint: &'static str,
+     pub local_ocr_note: &'static str,
+     pub local_ocr_fallback_label: &'static str,
+     pub local_ocr_fallback_tooltip: &'static str,
+     pub structured_output_label: &'static str,
+     pub structured_output_tooltip: &'static str,
+     pub output_display_field_tooltip: &'static str,
+     pub output_field_name_hint: &'static str,
+     pub output_field_desc_hint: &'static str,
+     pub output_add_field_btn: &'static str,
+     pub image_prep_section: &'static str,
+     pub image_max_dimension_label: &'static str,
+     pub image_upscale_below_label: &'static str,
+     pub image_zero_off_tooltip: &'static str,
+     pub image_grayscale_label: &'static str,
+     pub image_sharpen_label: &'static str,
+     pub image_threshold_label: &'static str,
+     pub image_format_label: &'static str,
+     pub image_quality_label: &'static str,
+     pub subtitle_format_label: &'static str,
+     pub subtitle_format_none: &'static str,
+     pub subtitle_export_label: &'static str,
      pub hotkeys_section: &'static str,
      // --- NEW FIELDS ---
      pub start_in_tray_label: &'static str,
@@ -92,7 +194,19 @@ impl LocaleText {
                  history_empty: "Chưa có lịch sử nào.",
                  clear_all_history_btn: "Dọn tất cả", 
                  view_image_btn: "Xem ảnh",
+                 view_processed_image_btn: "Ảnh đã xử lý",
+                 view_processed_image_tooltip: "Ảnh đúng như đã gửi cho mô hình",
                  listen_audio_btn: "Nghe audio",
+                 rerun_btn: "Chạy lại",
+                 rerun_preset_model: "Mô hình của cấu hình",
+                 rerun_original_label: "Bản gốc",
+                 timestamps_label: "Mốc thời gian",
+                 play_from_here_tooltip: "Phát từ đây",
+                 stop_playback_btn: "Dừng",
+                 continue_chat_btn: "Tiếp tục",
+                 conversation_label: "Các lần chỉnh sửa",
+                 chat_you_label: "Bạn:",
+                 chat_ai_label: "AI:",
                  prompt_mode_label: "Cách hoạt động:",
                  prompt_mode_fixed: "Làm theo lệnh sẵn",
                  prompt_mode_dynamic: "Viết lệnh tại chỗ",
@@ -101,6 +215,47 @@ impl LocaleText {
                  get_key_link: "Lấy tại console.groq.com",
                 gemini_api_key_label: "Mã API Gemini:",
                 gemini_get_key_link: "Lấy mã tại aistudio.google.com",
+                tts_section: "Đọc văn bản",
+                tts_provider_label: "Nhà cung cấp:",
+                tts_provider_gemini: "Gemini",
+                tts_provider_openai: "Tương thích OpenAI",
+                tts_provider_local: "Bộ đọc cục bộ",
+                tts_voice_label: "Giọng đọc:",
+                tts_voice_hint: "Để trống = mặc định",
+                tts_openai_url_label: "Địa chỉ endpoint:",
+                tts_openai_key_label: "API key (không bắt buộc):",
+                tts_openai_model_label: "Mô hình:",
+                tts_local_note: "Bộ đọc cục bộ chưa có trong phiên bản này.",
+                result_cache_section: "Bộ nhớ đệm kết quả",
+                result_cache_ttl_label: "Giữ (giờ):",
+                result_cache_ttl_tooltip: "0 = không hết hạn",
+                result_cache_max_label: "Tối đa:",
+                result_cache_max_tooltip: "0 = tắt bộ nhớ đệm",
+                result_cache_entries_label: "Kết quả đã lưu:",
+                result_cache_clear_btn: "Xóa bộ nhớ đệm",
+                cache_results_label: "Dùng lại kết quả đã lưu",
+                cache_results_tooltip: "Hiện kết quả đã lưu khi gửi lại cùng hình ảnh hoặc bản ghi âm với cùng câu lệnh và mô hình, không gọi API",
+                capture_mode_label: "Chụp:",
+                capture_mode_select: "Kéo chọn vùng",
+                capture_mode_last: "Dùng lại vùng trước",
+                capture_mode_fixed: "Vùng cố định",
+                capture_mode_active_window: "Cửa sổ đang dùng",
+                capture_mode_cursor_window: "Cửa sổ dưới con trỏ",
+                capture_mode_monitor: "Màn hình",
+                multi_region_hint: "Giữ Ctrl khi kéo để thêm nhiều vùng; các vùng được gửi chung một yêu cầu khi thả Ctrl hoặc nhấn Enter.",
+                last_region_reselect_hint: "Shift + phím tắt để chọn vùng mới.",
+                capture_mode_tooltip: "Mọi chế độ trừ kéo chọn đều chụp ngay không cần màn hình chọn. Nếu không có vùng hoặc cửa sổ, phím tắt sẽ yêu cầu chọn vùng.",
+                fixed_region_none: "Chưa lưu vùng nào",
+                fixed_region_from_last_btn: "Dùng vùng vừa chọn",
+                fixed_region_from_last_tooltip: "Lưu vùng vừa chọn với cài đặt này làm vùng cố định",
+                watch_mode_label: "Theo dõi vùng",
+                watch_mode_tooltip: "Liên tục chụp vùng và gửi lại mỗi khi chữ thay đổi, cập nhật một cửa sổ kết quả. Nhấn lại phím tắt hoặc đóng cửa sổ để dừng.",
+                watch_interval_label: "Mỗi:",
+                watch_threshold_label: "Thay đổi:",
+                watch_threshold_tooltip: "Tỷ lệ điểm ảnh phải thay đổi trước khi gửi lại vùng",
+                scroll_capture_label: "Chụp cuộn trang",
+                scroll_capture_tooltip: "Sau khi chụp vùng, tự cuộn cửa sổ bên dưới và ghép các khung hình thành một ảnh dài (trang web dài, lịch sử trò chuyện). Giữ yên chuột cho đến khi xong.",
+                scroll_max_frames_label: "Số khung tối đa:",
                 presets_section: "Danh Sách Cấu Hình",
                 global_settings: "Cài Đặt Chung",
                 preset_name_label: "Tên Cấu Hình:",
@@ -134,7 +289,56 @@ impl LocaleText {
                 audio_source_label: "Nguồn âm thanh:",
                 audio_src_mic: "Microphone",
                 audio_src_device: "Âm thanh máy tính",
+                audio_src_mix: "Mic + Máy tính",
+                audio_src_mix_tooltip: "Ghi cả micro và âm thanh máy tính (ví dụ: cả hai phía cuộc gọi)",
+                audio_input_device_label: "Thiết bị vào:",
+                audio_output_device_label: "Thiết bị ra:",
+                audio_device_default: "Mặc định",
+                audio_device_missing: "chưa kết nối",
+                refresh_devices_btn: "Làm mới thiết bị",
                 hide_recording_ui_label: "Ẩn giao diện ghi âm",
+                auto_stop_label: "Tự dừng khi im lặng",
+                auto_stop_silence_label: "Thời gian im lặng:",
+                keep_original_audio_label: "Lưu âm thanh chất lượng gốc vào lịch sử",
+                audio_format_label: "Định dạng:",
+                audio_format_flac: "FLAC (nhẹ hơn)",
+                live_transcribe_label: "Phiên âm trực tiếp khi đang ghi",
+                live_transcribe_tooltip: "Mỗi câu được phiên âm (và dịch lại nếu bật) ngay khi bạn ngừng nói",
+                transcribe_file_btn: "Chép lời từ tệp...",
+                transcribe_file_tooltip: "Chép lời tệp WAV/FLAC/MP3/OGG có sẵn bằng preset này (cũng có thể kéo thả tệp vào cửa sổ)",
+                whisper_section: "Tùy chọn Whisper",
+                whisper_language_label: "Ngôn ngữ:",
+                whisper_temperature_label: "Nhiệt độ:",
+                whisper_prompt_label: "Từ vựng:",
+                whisper_prompt_hint: "Tên riêng, thuật ngữ, cách viết...",
+                segment_timestamps_label: "Mốc thời gian theo đoạn",
+                diarization_label: "Phân biệt người nói",
+                diarization_tooltip: "Yêu cầu Gemini chép lời kèm tên người nói và thời gian, dùng cho hội thoại",
+                overlay_blocks_label: "Hiện kết quả tại chỗ (dịch màn hình)",
+                overlay_blocks_tooltip: "Mỗi khối chữ được hiện đè lên vị trí gốc. Nếu không đọc được kết quả sẽ hiện một cửa sổ như thường.",
+                ocr_language_label: "Ngôn ngữ OCR:",
+                ocr_language_hint: "tự động (vd: ja)",
+                local_ocr_note: "OCR ngoại tuyến bỏ qua câu lệnh. Bật dịch lại bên dưới để dịch chữ đã trích xuất.",
+                local_ocr_fallback_label: "Dự phòng OCR ngoại tuyến",
+                local_ocr_fallback_tooltip: "Khi không có API key hoặc mạng, trích xuất chữ bằng Windows OCR. Bật dịch lại để dịch chữ đó.",
+                structured_output_label: "Kết quả dạng JSON",
+                structured_output_tooltip: "Mô hình trả về các khóa bên dưới dạng JSON; khóa được chọn sẽ được hiển thị và sao chép. Nếu kết quả không phải JSON hợp lệ thì hiện nguyên văn.",
+                output_display_field_tooltip: "Hiển thị và sao chép khóa này",
+                output_field_name_hint: "khóa",
+                output_field_desc_hint: "Mô hình cần điền gì vào đây",
+                output_add_field_btn: "+ Thêm khóa",
+                image_prep_section: "Xử lý ảnh trước khi gửi",
+                image_max_dimension_label: "Kích thước tối đa (px):",
+                image_upscale_below_label: "Phóng to nếu nhỏ hơn (px):",
+                image_zero_off_tooltip: "0 = tắt",
+                image_grayscale_label: "Ảnh xám",
+                image_sharpen_label: "Làm nét",
+                image_threshold_label: "Ngưỡng đen trắng:",
+                image_format_label: "Định dạng gửi:",
+                image_quality_label: "Chất lượng:",
+                subtitle_format_label: "Phụ đề:",
+                subtitle_format_none: "Tắt",
+                subtitle_export_label: "Xuất phụ đề:",
                 hotkeys_section: "Phím tắt",
                 // --- NEW FIELDS VIETNAMESE ---
                 start_in_tray_label: "Khởi động trong tray",
@@ -174,7 +378,19 @@ impl LocaleText {
                  history_empty: "기록이 없습니다.",
                  clear_all_history_btn: "모두 삭제",
                  view_image_btn: "이미지 보기",
+                 view_processed_image_btn: "처리된 이미지",
+                 view_processed_image_tooltip: "모델에 전송된 그대로의 이미지",
                  listen_audio_btn: "오디오 듣기",
+                 rerun_btn: "다시 실행",
+                 rerun_preset_model: "프리셋 모델",
+                 rerun_original_label: "원본",
+                 timestamps_label: "타임스탬프",
+                 play_from_here_tooltip: "여기서부터 재생",
+                 stop_playback_btn: "정지",
+                 continue_chat_btn: "계속",
+                 conversation_label: "수정 내역",
+                 chat_you_label: "나:",
+                 chat_ai_label: "AI:",
                  prompt_mode_label: "작동 방식:",
                  prompt_mode_fixed: "사전 정의된 프롬프트",
                  prompt_mode_dynamic: "즉석에서 작성",
@@ -183,6 +399,47 @@ impl LocaleText {
                 get_key_link: "console.groq.com에서 API 키 받기",
                 gemini_api_key_label: "Gemini API 키:",
                 gemini_get_key_link: "aistudio.google.com에서 API 키 받기",
+                tts_section: "텍스트 음성 변환",
+                tts_provider_label: "제공자:",
+                tts_provider_gemini: "Gemini",
+                tts_provider_openai: "OpenAI 호환",
+                tts_provider_local: "로컬 엔진",
+                tts_voice_label: "음성:",
+                tts_voice_hint: "비워 두면 기본값",
+                tts_openai_url_label: "엔드포인트 URL:",
+                tts_openai_key_label: "API 키 (선택):",
+                tts_openai_model_label: "모델:",
+                tts_local_note: "이 빌드에서는 아직 로컬 엔진을 사용할 수 없습니다.",
+                result_cache_section: "결과 캐시",
+                result_cache_ttl_label: "보관 (시간):",
+                result_cache_ttl_tooltip: "0 = 만료 없음",
+                result_cache_max_label: "최대 항목:",
+                result_cache_max_tooltip: "0 = 캐시 끄기",
+                result_cache_entries_label: "저장된 결과:",
+                result_cache_clear_btn: "캐시 지우기",
+                cache_results_label: "캐시된 결과 재사용",
+                cache_results_tooltip: "같은 이미지나 녹음을 같은 프롬프트와 모델로 다시 보내면 API 호출 없이 저장된 결과를 표시합니다",
+                capture_mode_label: "캡처:",
+                capture_mode_select: "드래그로 선택",
+                capture_mode_last: "마지막 영역 재사용",
+                capture_mode_fixed: "고정 영역",
+                capture_mode_active_window: "활성 창",
+                capture_mode_cursor_window: "커서 아래 창",
+                capture_mode_monitor: "모니터",
+                multi_region_hint: "Ctrl을 누른 채 드래그하면 여러 영역을 추가할 수 있으며, Ctrl을 놓거나 Enter를 누르면 한 번의 요청으로 보냅니다.",
+                last_region_reselect_hint: "Shift + 단축키로 새 영역을 선택합니다.",
+                capture_mode_tooltip: "드래그 선택 외의 모든 모드는 선택 화면 없이 바로 캡처합니다. 영역이나 창을 사용할 수 없으면 단축키가 선택을 요청합니다.",
+                fixed_region_none: "저장된 영역 없음",
+                fixed_region_from_last_btn: "마지막 선택 사용",
+                fixed_region_from_last_tooltip: "이 프리셋으로 마지막에 선택한 영역을 고정 영역으로 저장합니다",
+                watch_mode_label: "영역 감시",
+                watch_mode_tooltip: "영역을 계속 캡처하여 텍스트가 바뀔 때마다 다시 보내고 결과 창 하나를 갱신합니다. 단축키를 다시 누르거나 창을 닫으면 멈춥니다.",
+                watch_interval_label: "간격:",
+                watch_threshold_label: "변화:",
+                watch_threshold_tooltip: "영역을 다시 보내기 전에 바뀌어야 하는 픽셀 비율",
+                scroll_capture_label: "스크롤 캡처",
+                scroll_capture_tooltip: "영역을 캡처한 뒤 아래 창을 스크롤하며 프레임을 하나의 긴 이미지로 이어 붙입니다 (긴 웹 페이지, 채팅 기록). 끝날 때까지 마우스를 움직이지 마세요.",
+                scroll_max_frames_label: "최대 프레임:",
                 presets_section: "프리셋 목록",
                 global_settings: "전역 설정",
                 preset_name_label: "프리셋 이름:",
@@ -216,7 +473,56 @@ impl LocaleText {
                 audio_source_label: "오디오 소스:",
                 audio_src_mic: "마이크",
                 audio_src_device: "컴퓨터 오디오",
+                audio_src_mix: "마이크 + 시스템",
+                audio_src_mix_tooltip: "마이크와 시스템 소리를 함께 녹음합니다 (예: 통화 양쪽)",
+                audio_input_device_label: "입력 장치:",
+                audio_output_device_label: "출력 장치:",
+                audio_device_default: "기본값",
+                audio_device_missing: "연결되지 않음",
+                refresh_devices_btn: "장치 새로고침",
                 hide_recording_ui_label: "녹음 UI 숨기기",
+                auto_stop_label: "무음 시 자동 중지",
+                auto_stop_silence_label: "무음 시간:",
+                keep_original_audio_label: "기록에 원본 음질 오디오 보관",
+                audio_format_label: "형식:",
+                audio_format_flac: "FLAC (더 작음)",
+                live_transcribe_label: "녹음 중 실시간 전사",
+                live_transcribe_tooltip: "말을 멈출 때마다 문장을 바로 전사합니다 (재번역이 켜져 있으면 번역도 함께)",
+                transcribe_file_btn: "파일 변환...",
+                transcribe_file_tooltip: "이 프리셋으로 기존 WAV/FLAC/MP3/OGG 파일을 변환합니다 (창에 파일을 끌어다 놓아도 됩니다)",
+                whisper_section: "Whisper 옵션",
+                whisper_language_label: "언어:",
+                whisper_temperature_label: "온도:",
+                whisper_prompt_label: "어휘:",
+                whisper_prompt_hint: "이름, 전문 용어, 철자 힌트...",
+                segment_timestamps_label: "구간 타임스탬프",
+                diarization_label: "화자 구분",
+                diarization_tooltip: "대화용: Gemini에 화자와 시간이 표시된 전사를 요청합니다",
+                overlay_blocks_label: "제자리에 결과 표시 (화면 번역)",
+                overlay_blocks_tooltip: "각 텍스트 블록을 원래 위치 위에 표시합니다. 응답을 읽을 수 없으면 하나의 창으로 표시합니다.",
+                ocr_language_label: "OCR 언어:",
+                ocr_language_hint: "자동 (예: ja)",
+                local_ocr_note: "오프라인 OCR은 프롬프트를 무시합니다. 추출한 텍스트를 번역하려면 아래 재번역을 켜세요.",
+                local_ocr_fallback_label: "오프라인 OCR 대체",
+                local_ocr_fallback_tooltip: "API 키나 연결이 없으면 Windows OCR로 텍스트를 추출합니다. 번역하려면 재번역을 켜세요.",
+                structured_output_label: "구조화된 출력 (JSON)",
+                structured_output_tooltip: "모델이 아래 키를 JSON 객체로 반환하며, 선택한 키가 표시되고 복사됩니다. 올바른 JSON이 아니면 그대로 표시됩니다.",
+                output_display_field_tooltip: "이 키를 표시하고 복사",
+                output_field_name_hint: "키",
+                output_field_desc_hint: "모델이 채울 내용",
+                output_add_field_btn: "+ 키 추가",
+                image_prep_section: "이미지 전처리",
+                image_max_dimension_label: "최대 크기 (px):",
+                image_upscale_below_label: "확대 기준 (px):",
+                image_zero_off_tooltip: "0 = 끄기",
+                image_grayscale_label: "흑백",
+                image_sharpen_label: "선명하게",
+                image_threshold_label: "이진화 임계값:",
+                image_format_label: "업로드 형식:",
+                image_quality_label: "품질:",
+                subtitle_format_label: "자막:",
+                subtitle_format_none: "끄기",
+                subtitle_export_label: "자막 내보내기:",
                 hotkeys_section: "단축키",
                 // --- NEW FIELDS KOREAN ---
                 start_in_tray_label: "트레이로 시작",
@@ -256,7 +562,19 @@ impl LocaleText {
                  history_empty: "No history yet.",
                  clear_all_history_btn: "Clear All",
                  view_image_btn: "View Image",
+                 view_processed_image_btn: "Processed image",
+                 view_processed_image_tooltip: "The image exactly as it was sent to the model",
                  listen_audio_btn: "Listen Audio",
+                 rerun_btn: "Re-run",
+                 rerun_preset_model: "Preset model",
+                 rerun_original_label: "Original",
+                 timestamps_label: "Timestamps",
+                 play_from_here_tooltip: "Play from here",
+                 stop_playback_btn: "Stop",
+                 continue_chat_btn: "Continue",
+                 conversation_label: "Refinements",
+                 chat_you_label: "You:",
+                 chat_ai_label: "AI:",
                  prompt_mode_label: "Prompt Mode:",
                  prompt_mode_fixed: "Predefined Prompt",
                  prompt_mode_dynamic: "Write on the spot",
@@ -265,6 +583,47 @@ impl LocaleText {
                 get_key_link: "Get API Key at console.groq.com",
                 gemini_api_key_label: "Gemini API Key:",
                 gemini_get_key_link: "Get API Key at aistudio.google.com",
+                tts_section: "Text-to-speech",
+                tts_provider_label: "Provider:",
+                tts_provider_gemini: "Gemini",
+                tts_provider_openai: "OpenAI-compatible",
+                tts_provider_local: "Local engine",
+                tts_voice_label: "Voice:",
+                tts_voice_hint: "Empty = provider default",
+                tts_openai_url_label: "Endpoint URL:",
+                tts_openai_key_label: "API key (optional):",
+                tts_openai_model_label: "Model:",
+                tts_local_note: "The local engine is not available in this build yet.",
+                result_cache_section: "Result cache",
+                result_cache_ttl_label: "Keep (hours):",
+                result_cache_ttl_tooltip: "0 = never expires",
+                result_cache_max_label: "Max entries:",
+                result_cache_max_tooltip: "0 = cache disabled",
+                result_cache_entries_label: "Stored answers:",
+                result_cache_clear_btn: "Clear cache",
+                cache_results_label: "Reuse cached results",
+                cache_results_tooltip: "Show the stored answer when the same image or recording is sent again with the same prompt and model, without calling the API",
+                capture_mode_label: "Capture:",
+                capture_mode_select: "Drag to select",
+                capture_mode_last: "Reuse last region",
+                capture_mode_fixed: "Fixed region",
+                capture_mode_active_window: "Active window",
+                capture_mode_cursor_window: "Window under cursor",
+                capture_mode_monitor: "Monitor",
+                multi_region_hint: "Hold Ctrl while dragging to add several regions; they are sent as one request when you release Ctrl or press Enter.",
+                last_region_reselect_hint: "Shift + hotkey selects a new region.",
+                capture_mode_tooltip: "All modes except drag-select capture instantly without the selection screen. If the region or window is not available, the hotkey asks for a selection.",
+                fixed_region_none: "No region saved",
+                fixed_region_from_last_btn: "Use last selection",
+                fixed_region_from_last_tooltip: "Saves the region last selected with this preset as its fixed region",
+                watch_mode_label: "Watch region",
+                watch_mode_tooltip: "Keeps capturing the region and sends it again whenever the text changes, updating one result window. Press the hotkey again or close the window to stop.",
+                watch_interval_label: "Every:",
+                watch_threshold_label: "Change:",
+                watch_threshold_tooltip: "Share of pixels that must change before the region is sent again",
+                scroll_capture_label: "Scrolling capture",
+                scroll_capture_tooltip: "After the region is captured, scrolls the window under it and stitches the frames into one tall image (long web pages, chat logs). Keep the mouse still until it finishes.",
+                scroll_max_frames_label: "Max frames:",
                 presets_section: "Presets",
                 global_settings: "Global Settings",
                 preset_name_label: "Preset Name:",
@@ -298,7 +657,56 @@ impl LocaleText {
                 audio_source_label: "Audio Source:",
                 audio_src_mic: "Microphone",
                 audio_src_device: "Device Audio",
+                audio_src_mix: "Mic + System",
+                audio_src_mix_tooltip: "Records the microphone and system audio together (e.g. both sides of a call)",
+                audio_input_device_label: "Input device:",
+                audio_output_device_label: "Output device:",
+                audio_device_default: "Default",
+                audio_device_missing: "not connected",
+                refresh_devices_btn: "Refresh devices",
                 hide_recording_ui_label: "Hide Recording UI",
+                auto_stop_label: "Auto-stop on silence",
+                auto_stop_silence_label: "Silence:",
+                keep_original_audio_label: "Keep original quality audio in history",
+                audio_format_label: "Format:",
+                audio_format_flac: "FLAC (smaller)",
+                live_transcribe_label: "Live transcription while recording",
+                live_transcribe_tooltip: "Each sentence is transcribed (and retranslated if enabled) as soon as you pause",
+                transcribe_file_btn: "Transcribe file...",
+                transcribe_file_tooltip: "Transcribe an existing WAV/FLAC/MP3/OGG file with this preset (you can also drop files on this window)",
+                whisper_section: "Whisper options",
+                whisper_language_label: "Language:",
+                whisper_temperature_label: "Temperature:",
+                whisper_prompt_label: "Vocabulary:",
+                whisper_prompt_hint: "Names, jargon, spelling hints...",
+                segment_timestamps_label: "Segment timestamps",
+                diarization_label: "Label speakers (diarization)",
+                diarization_tooltip: "Asks Gemini for a speaker-labelled transcript with timings, for conversations",
+                overlay_blocks_label: "Show results in place (screen translation)",
+                overlay_blocks_tooltip: "Each text block is drawn over its original position. Falls back to a single window if the reply cannot be read.",
+                ocr_language_label: "OCR language:",
+                ocr_language_hint: "auto (e.g. ja)",
+                local_ocr_note: "Offline OCR ignores the prompt. Enable retranslate below to translate the extracted text.",
+                local_ocr_fallback_label: "Offline OCR fallback",
+                local_ocr_fallback_tooltip: "Without an API key or connection, extract the text with Windows OCR instead. Enable retranslate to translate it.",
+                structured_output_label: "Structured output (JSON)",
+                structured_output_tooltip: "The model returns the keys below as a JSON object; the selected key is shown and copied. If the reply is not valid JSON it is shown as is.",
+                output_display_field_tooltip: "Show and copy this key",
+                output_field_name_hint: "key",
+                output_field_desc_hint: "What the model should put here",
+                output_add_field_btn: "+ Add key",
+                image_prep_section: "Image preprocessing",
+                image_max_dimension_label: "Max size (px):",
+                image_upscale_below_label: "Upscale below (px):",
+                image_zero_off_tooltip: "0 = off",
+                image_grayscale_label: "Grayscale",
+                image_sharpen_label: "Sharpen",
+                image_threshold_label: "Threshold:",
+                image_format_label: "Upload format:",
+                image_quality_label: "Quality:",
+                subtitle_format_label: "Subtitles:",
+                subtitle_format_none: "Off",
+                subtitle_export_label: "Export subtitles:",
                 hotkeys_section: "Hotkeys",
                 // --- NEW FIELDS ENGLISH ---
                 start_in_tray_label: "Start in tray",
@@ -53,6 +53,20 @@ pub fn render_global_settings(
         });
     });
 
+    ui.add_space(10.0);
+
+    // Text-to-speech
+    if render_tts_settings(ui, config, text) {
+        changed = true;
+    }
+
+    ui.add_space(10.0);
+
+    // Result cache
+    if render_result_cache_settings(ui, config, text) {
+        changed = true;
+    }
+
     ui.add_space(10.0);
     
     // Usage Statistics
@@ -180,12 +194,14 @@ pub fn render_global_settings(
     if ui.button(text.reset_defaults_btn).clicked() {
         let saved_groq_key = config.api_key.clone();
         let saved_gemini_key = config.gemini_api_key.clone();
+        let saved_tts_key = config.tts_openai_api_key.clone();
         let saved_language = config.ui_language.clone();
         
         *config = Config::default();
         
         config.api_key = saved_groq_key;
         config.gemini_api_key = saved_gemini_key;
+        config.tts_openai_api_key = saved_tts_key;
         config.ui_language = saved_language;
         changed = true;
     }
@@ -193,6 +209,79 @@ pub fn render_global_settings(
     changed
 }
 
+fn render_tts_settings(ui: &mut egui::Ui, config: &mut Config, text: &LocaleText) -> bool {
+    let mut changed = false;
+    ui.group(|ui| {
+        ui.label(egui::RichText::new(text.tts_section).strong());
+        ui.horizontal(|ui| {
+            ui.label(text.tts_provider_label);
+            for (value, label) in [("gemini", text.tts_provider_gemini), ("openai", text.tts_provider_openai), ("local", text.tts_provider_local)] {
+                if ui.radio_value(&mut config.tts_provider, value.to_string(), label).clicked() {
+                    changed = true;
+                }
+            }
+        });
+
+        if config.tts_provider == "local" {
+            ui.label(egui::RichText::new(text.tts_local_note).size(11.0).color(egui::Color32::GRAY));
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(text.tts_voice_label);
+            if ui.add(egui::TextEdit::singleline(&mut config.tts_voice).hint_text(text.tts_voice_hint).desired_width(160.0)).changed() {
+                changed = true;
+            }
+        });
+
+        if config.tts_provider == "openai" {
+            ui.horizontal(|ui| {
+                ui.label(text.tts_openai_url_label);
+                if ui.add(egui::TextEdit::singleline(&mut config.tts_openai_url).desired_width(260.0)).changed() {
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(text.tts_openai_model_label);
+                if ui.add(egui::TextEdit::singleline(&mut config.tts_openai_model).desired_width(160.0)).changed() {
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(text.tts_openai_key_label);
+                if ui.add(egui::TextEdit::singleline(&mut config.tts_openai_api_key).password(true).desired_width(220.0)).changed() {
+                    changed = true;
+                }
+            });
+        }
+    });
+    changed
+}
+
+fn render_result_cache_settings(ui: &mut egui::Ui, config: &mut Config, text: &LocaleText) -> bool {
+    let mut changed = false;
+    ui.group(|ui| {
+        ui.label(egui::RichText::new(text.result_cache_section).strong());
+        ui.horizontal(|ui| {
+            ui.label(text.result_cache_ttl_label);
+            if ui.add(egui::DragValue::new(&mut config.result_cache_ttl_hours).clamp_range(0..=8760)).on_hover_text(text.result_cache_ttl_tooltip).changed() {
+                changed = true;
+            }
+            ui.label(text.result_cache_max_label);
+            if ui.add(egui::DragValue::new(&mut config.result_cache_max_entries).clamp_range(0..=10000).speed(5.0)).on_hover_text(text.result_cache_max_tooltip).changed() {
+                changed = true;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{} {}", text.result_cache_entries_label, crate::result_cache::entry_count()));
+            if ui.button(text.result_cache_clear_btn).clicked() {
+                crate::result_cache::clear();
+            }
+        });
+    });
+    changed
+}
+
 fn render_usage_statistics(
     ui: &mut egui::Ui, 
     usage_stats: &HashMap<String, String>, 
@@ -3,6 +3,9 @@ use crate::config::Config;
 use crate::gui::locale::LocaleText;
 use crate::gui::icons::{Icon, icon_button, draw_icon_static};
 use crate::history::{HistoryManager, HistoryItem, HistoryType};
+use crate::model_config::{get_all_models, get_model_by_id, ModelType};
+use crate::api::types::{format_timestamp, speakers_of};
+use crate::subtitle::SubtitleFormat;
 
 pub fn render_history_panel(
     ui: &mut egui::Ui,
@@ -55,8 +58,21 @@ pub fn render_history_panel(
     
     let items = history_manager.items.lock().unwrap().clone();
     let q = search_query.to_lowercase();
+    let matches = |i: &HistoryItem| q.is_empty() || i.text.to_lowercase().contains(&q) || i.timestamp.contains(&q)
+        || i.conversation.iter().any(|t| t.content.to_lowercase().contains(&q));
+
+    // Re-run results are grouped under their original entry
+    let children_of = |id: i64| -> Vec<&HistoryItem> {
+        let mut children: Vec<&HistoryItem> = items.iter().filter(|c| c.parent_id == Some(id)).collect();
+        children.reverse(); // Oldest re-run first
+        children
+    };
     let filtered: Vec<&HistoryItem> = items.iter().filter(|i| {
-        q.is_empty() || i.text.to_lowercase().contains(&q) || i.timestamp.contains(&q)
+        let is_root = match i.parent_id {
+            Some(pid) => !items.iter().any(|p| p.id == pid),
+            None => true,
+        };
+        is_root && (matches(i) || children_of(i.id).into_iter().any(|c| matches(c)))
     }).collect();
 
     if filtered.is_empty() {
@@ -94,14 +110,65 @@ pub fn render_history_panel(
                                 HistoryType::Audio => text.listen_audio_btn,
                             };
                             if ui.button(btn_text).clicked() {
-                                let config_dir = dirs::config_dir().unwrap().join("screen-goated-toolbox").join("history_media");
-                                let path = config_dir.join(&item.media_path);
+                                let path = crate::history::get_media_path(&item.media_path);
                                 let _ = open::that(path);
                             }
+
+                            if !item.processed_media_path.is_empty() && ui.button(text.view_processed_image_btn).on_hover_text(text.view_processed_image_tooltip).clicked() {
+                                let path = crate::history::get_media_path(&item.processed_media_path);
+                                let _ = open::that(path);
+                            }
+
+                            render_rerun_menu(ui, config, item, text);
+
+                            if ui.button(text.continue_chat_btn).clicked() {
+                                continue_conversation(item);
+                            }
                         });
                     });
                     
-                    ui.label(egui::RichText::new(&item.text).size(13.0));
+                    let children = children_of(item.id);
+                    if children.is_empty() {
+                        ui.label(egui::RichText::new(&item.text).size(13.0));
+                        render_segments(ui, item, text);
+                        render_conversation(ui, item, text);
+                    } else {
+                        // Side-by-side comparison of the original and its re-runs
+                        egui::ScrollArea::horizontal().id_source(("rerun_cmp", item.id)).show(ui, |ui| {
+                            ui.horizontal_top(|ui| {
+                                let col_w = 220.0;
+                                ui.vertical(|ui| {
+                                    ui.set_width(col_w);
+                                    ui.label(egui::RichText::new(text.rerun_original_label).size(10.0).strong());
+                                    ui.label(egui::RichText::new(&item.text).size(13.0));
+                                    render_segments(ui, item, text);
+                                    render_conversation(ui, item, text);
+                                });
+                                for child in children {
+                                    ui.separator();
+                                    ui.vertical(|ui| {
+                                        ui.set_width(col_w);
+                                        ui.horizontal(|ui| {
+                                            ui.label(egui::RichText::new(model_label(&child.model_id, &config.ui_language)).size(10.0).strong());
+                                            ui.label(egui::RichText::new(&child.timestamp).size(10.0).weak());
+                                            if icon_button(ui, Icon::Copy).on_hover_text("Copy Text").clicked() {
+                                                crate::gui::utils::copy_to_clipboard_text(&child.text);
+                                            }
+                                            if icon_button(ui, Icon::Close).on_hover_text("Delete").clicked() {
+                                                id_to_delete = Some(child.id);
+                                            }
+                                            if ui.small_button(text.continue_chat_btn).clicked() {
+                                                continue_conversation(child);
+                                            }
+                                        });
+                                        ui.label(egui::RichText::new(&child.text).size(13.0));
+                                        render_segments(ui, child, text);
+                                        render_conversation(ui, child, text);
+                                    });
+                                }
+                            });
+                        });
+                    }
                 });
                 ui.add_space(4.0);
             }
@@ -114,3 +181,141 @@ pub fn render_history_panel(
     
     changed
 }
+
+// Same palette as the result window labels
+const SPEAKER_COLORS: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(100, 200, 255),
+    egui::Color32::from_rgb(120, 220, 120),
+    egui::Color32::from_rgb(255, 180, 80),
+    egui::Color32::from_rgb(255, 140, 200),
+    egui::Color32::from_rgb(230, 230, 100),
+    egui::Color32::from_rgb(180, 150, 255),
+];
+
+// Segment timestamps (and speakers) of an audio entry
+fn render_segments(ui: &mut egui::Ui, item: &HistoryItem, text: &LocaleText) {
+    if item.segments.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new(format!("{} ({})", text.timestamps_label, item.segments.len()))
+        .id_source(("segments", item.id))
+        .show(ui, |ui| {
+            let speakers = speakers_of(&item.segments);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(text.subtitle_export_label).size(11.0));
+                for format in [SubtitleFormat::Srt, SubtitleFormat::Vtt] {
+                    if ui.small_button(format.extension().to_uppercase()).clicked() {
+                        export_subtitles(item, format);
+                    }
+                }
+                if ui.small_button(text.stop_playback_btn).clicked() {
+                    crate::audio::playback::stop_playback();
+                }
+            });
+            for seg in &item.segments {
+                ui.horizontal_wrapped(|ui| {
+                    if ui.small_button("▶").on_hover_text(text.play_from_here_tooltip).clicked() {
+                        crate::audio::playback::play_file_from(crate::history::get_media_path(&item.media_path), seg.start);
+                    }
+                    let stamp = format!("{} - {}", format_timestamp(seg.start), format_timestamp(seg.end));
+                    if ui.small_button(stamp).on_hover_text("Copy Text").clicked() {
+                        crate::gui::utils::copy_to_clipboard_text(&seg.text);
+                    }
+                    if !seg.speaker.is_empty() {
+                        let idx = speakers.iter().position(|s| s == &seg.speaker).unwrap_or(0);
+                        ui.label(egui::RichText::new(format!("{}:", seg.speaker)).size(13.0).strong().color(SPEAKER_COLORS[idx % SPEAKER_COLORS.len()]));
+                    }
+                    ui.label(egui::RichText::new(&seg.text).size(13.0));
+                });
+            }
+        });
+}
+
+// Original track plus the translated one when the entry was retranslated, then shows the folder
+fn export_subtitles(item: &HistoryItem, format: SubtitleFormat) {
+    let stem = format!("subtitles_{}", item.id);
+    match crate::subtitle::export_tracks(&item.segments, &item.translation, &item.translation_lang, format, &stem) {
+        Ok(_) => { let _ = open::that(crate::subtitle::export_dir()); },
+        Err(e) => eprintln!("Subtitle export error: {}", e),
+    }
+}
+
+// Follow-up refinements saved from the result window
+fn render_conversation(ui: &mut egui::Ui, item: &HistoryItem, text: &LocaleText) {
+    // The first turn is the original result, shown above already
+    if item.conversation.len() < 2 {
+        return;
+    }
+    let turns = (item.conversation.len() - 1) / 2;
+    egui::CollapsingHeader::new(format!("{} ({})", text.conversation_label, turns))
+        .id_source(("conversation", item.id))
+        .show(ui, |ui| {
+            for turn in item.conversation.iter().skip(1) {
+                let (label, color) = if turn.role == "user" {
+                    (text.chat_you_label, egui::Color32::from_rgb(100, 160, 255))
+                } else {
+                    (text.chat_ai_label, egui::Color32::from_rgb(120, 200, 120))
+                };
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new(label).size(11.0).strong().color(color));
+                    ui.label(egui::RichText::new(&turn.content).size(13.0));
+                });
+            }
+        });
+}
+
+fn continue_conversation(item: &HistoryItem) {
+    let item = item.clone();
+    std::thread::spawn(move || {
+        crate::overlay::process::reopen_history_conversation(item);
+    });
+}
+
+fn model_label(model_id: &str, ui_language: &str) -> String {
+    match get_model_by_id(model_id) {
+        Some(m) => match ui_language {
+            "vi" => m.name_vi,
+            "ko" => m.name_ko,
+            _ => m.name_en,
+        },
+        None => model_id.to_string(),
+    }
+}
+
+// Re-run menu: Preset -> Model. The first entry keeps the preset's own model.
+fn render_rerun_menu(ui: &mut egui::Ui, config: &Config, item: &HistoryItem, text: &LocaleText) {
+    let (preset_type, model_type) = match item.item_type {
+        HistoryType::Image => ("image", ModelType::Vision),
+        HistoryType::Audio => ("audio", ModelType::Audio),
+    };
+
+    ui.menu_button(text.rerun_btn, |ui| {
+        for preset in config.presets.iter().filter(|p| p.preset_type == preset_type && !p.is_upcoming) {
+            ui.menu_button(preset.name.as_str(), |ui| {
+                let mut chosen_model = None;
+                if ui.button(format!("{} ({})", text.rerun_preset_model, model_label(&preset.model, &config.ui_language))).clicked() {
+                    chosen_model = Some(preset.model.clone());
+                }
+                ui.separator();
+                for model in get_all_models() {
+                    if model.enabled && model.model_type == model_type {
+                        let label = format!("{} ({})", model_label(&model.id, &config.ui_language), model.full_name);
+                        if ui.button(label).clicked() {
+                            chosen_model = Some(model.id.clone());
+                        }
+                    }
+                }
+
+                if let Some(model_id) = chosen_model {
+                    let mut rerun_preset = preset.clone();
+                    rerun_preset.model = model_id;
+                    let rerun_item = item.clone();
+                    std::thread::spawn(move || {
+                        crate::overlay::process::rerun_history_item(rerun_item, rerun_preset);
+                    });
+                    ui.close_menu();
+                }
+            });
+        }
+    });
+}
@@ -1,5 +1,5 @@
 use eframe::egui;
-use crate::config::{Config, get_all_languages};
+use crate::config::{Config, OutputField, get_all_languages};
 use crate::gui::locale::LocaleText;
 use crate::gui::icons::{Icon, icon_button};
 use crate::model_config::{get_all_models, ModelType, get_model_by_id};
@@ -10,6 +10,7 @@ pub fn render_preset_editor(
     preset_idx: usize,
     search_query: &mut String,
     cached_monitors: &mut Vec<String>,
+    cached_audio_devices: &mut (Vec<String>, Vec<String>),
     recording_hotkey_for_preset: &mut Option<usize>,
     hotkey_conflict_msg: &Option<String>,
     text: &LocaleText,
@@ -79,6 +80,97 @@ pub fn render_preset_editor(
          }
      });
 
+    // Capture mode: drag a selection, or capture a remembered region, a window or a monitor instantly
+    if is_image {
+        ui.horizontal(|ui| {
+            ui.label(text.capture_mode_label);
+            let modes = [
+                ("select", text.capture_mode_select),
+                ("last", text.capture_mode_last),
+                ("fixed", text.capture_mode_fixed),
+                ("active_window", text.capture_mode_active_window),
+                ("cursor_window", text.capture_mode_cursor_window),
+            ];
+            let selected = match preset.capture_mode.strip_prefix("monitor:") {
+                Some(monitor) => format!("{} ({})", text.capture_mode_monitor, monitor),
+                None => modes.iter().find(|(v, _)| *v == preset.capture_mode).map_or(text.capture_mode_select, |(_, l)| *l).to_string(),
+            };
+            egui::ComboBox::from_id_source("capture_mode_combo")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (value, label) in modes {
+                        if ui.selectable_value(&mut preset.capture_mode, value.to_string(), label).clicked() {
+                            changed = true;
+                        }
+                    }
+                    for monitor in cached_monitors.iter() {
+                        let label = format!("{} ({})", text.capture_mode_monitor, monitor);
+                        if ui.selectable_value(&mut preset.capture_mode, format!("monitor:{}", monitor), label).clicked() {
+                            changed = true;
+                        }
+                    }
+                }).response.on_hover_text(text.capture_mode_tooltip);
+            if icon_button(ui, Icon::Refresh).on_hover_text("Refresh Monitors").clicked() {
+                *cached_monitors = crate::gui::utils::get_monitor_names();
+            }
+        });
+        if preset.capture_mode == "select" {
+            ui.label(egui::RichText::new(text.multi_region_hint).size(11.0).color(egui::Color32::GRAY));
+        }
+        if preset.capture_mode == "last" {
+            ui.label(egui::RichText::new(text.last_region_reselect_hint).size(11.0).color(egui::Color32::GRAY));
+        }
+        if preset.capture_mode == "fixed" {
+            ui.horizontal(|ui| {
+                match &preset.fixed_region {
+                    Some(r) => ui.label(format!("{}  {}x{} @ ({}, {})", r.monitor, r.right - r.left, r.bottom - r.top, r.left, r.top)),
+                    None => ui.label(egui::RichText::new(text.fixed_region_none).color(egui::Color32::GRAY)),
+                };
+                // The overlay records selections on the shared config, not on this copy
+                let last_region = crate::APP.lock().ok()
+                    .and_then(|app| app.config.presets.iter().find(|p| p.id == preset.id).and_then(|p| p.last_region.clone()));
+                if ui.add_enabled(last_region.is_some(), egui::Button::new(text.fixed_region_from_last_btn)).on_hover_text(text.fixed_region_from_last_tooltip).clicked() {
+                    preset.fixed_region = last_region;
+                    changed = true;
+                }
+            });
+        }
+
+        // Scrolling capture: stitched long pages (watch mode takes precedence)
+        if !preset.watch_mode || preset.prompt_mode == "dynamic" {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut preset.scroll_capture, text.scroll_capture_label).on_hover_text(text.scroll_capture_tooltip).clicked() {
+                    changed = true;
+                }
+                if preset.scroll_capture {
+                    ui.label(text.scroll_max_frames_label);
+                    if ui.add(egui::DragValue::new(&mut preset.scroll_max_frames).clamp_range(2..=100)).changed() {
+                        changed = true;
+                    }
+                }
+            });
+        }
+
+        // Watch mode: live re-translation of the captured region
+        if preset.prompt_mode != "dynamic" {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut preset.watch_mode, text.watch_mode_label).on_hover_text(text.watch_mode_tooltip).clicked() {
+                    changed = true;
+                }
+                if preset.watch_mode {
+                    ui.label(text.watch_interval_label);
+                    if ui.add(egui::DragValue::new(&mut preset.watch_interval_ms).clamp_range(200..=10000).speed(50.0).suffix(" ms")).changed() {
+                        changed = true;
+                    }
+                    ui.label(text.watch_threshold_label);
+                    if ui.add(egui::DragValue::new(&mut preset.watch_threshold).clamp_range(0.1..=50.0).speed(0.1).suffix(" %")).on_hover_text(text.watch_threshold_tooltip).changed() {
+                        changed = true;
+                    }
+                }
+            });
+        }
+    }
+
     if is_video {
         // Video Placeholder UI
         ui.group(|ui| {
@@ -203,10 +295,73 @@ pub fn render_preset_editor(
                     if ui.radio_value(&mut preset.audio_source, "device".to_string(), text.audio_src_device).clicked() {
                         changed = true;
                     }
+                    if ui.radio_value(&mut preset.audio_source, "mix".to_string(), text.audio_src_mix).on_hover_text(text.audio_src_mix_tooltip).clicked() {
+                        changed = true;
+                    }
                     if ui.checkbox(&mut preset.hide_recording_ui, text.hide_recording_ui_label).clicked() {
                         changed = true;
                     }
                 });
+                // Device pickers (names are enumerated once and refreshed on demand)
+                let (input_names, output_names) = cached_audio_devices;
+                if preset.audio_source != "device" {
+                    ui.horizontal(|ui| {
+                        ui.label(text.audio_input_device_label);
+                        if device_combo(ui, ("input_device", preset_idx), &mut preset.audio_input_device, input_names, text) {
+                            changed = true;
+                        }
+                    });
+                }
+                if preset.audio_source != "mic" {
+                    ui.horizontal(|ui| {
+                        ui.label(text.audio_output_device_label);
+                        if device_combo(ui, ("output_device", preset_idx), &mut preset.audio_output_device, output_names, text) {
+                            changed = true;
+                        }
+                    });
+                }
+                if ui.small_button(text.refresh_devices_btn).clicked() {
+                    *input_names = crate::audio::device::input_device_names();
+                    *output_names = crate::audio::device::output_device_names();
+                }
+                ui.horizontal(|ui| {
+                    let mut auto_stop = preset.auto_stop_silence_secs > 0.0;
+                    if ui.checkbox(&mut auto_stop, text.auto_stop_label).clicked() {
+                        preset.auto_stop_silence_secs = if auto_stop { 2.0 } else { 0.0 };
+                        changed = true;
+                    }
+                    if auto_stop {
+                        ui.label(text.auto_stop_silence_label);
+                        if ui.add(egui::Slider::new(&mut preset.auto_stop_silence_secs, 0.5..=10.0).suffix("s")).changed() {
+                            changed = true;
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(text.audio_format_label);
+                    if ui.radio_value(&mut preset.audio_format, "wav".to_string(), "WAV").clicked() {
+                        changed = true;
+                    }
+                    if ui.radio_value(&mut preset.audio_format, "flac".to_string(), text.audio_format_flac).clicked() {
+                        changed = true;
+                    }
+                    if ui.checkbox(&mut preset.keep_original_audio, text.keep_original_audio_label).clicked() {
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut preset.live_transcribe, text.live_transcribe_label).on_hover_text(text.live_transcribe_tooltip).clicked() {
+                        changed = true;
+                    }
+                    if ui.button(text.transcribe_file_btn).on_hover_text(text.transcribe_file_tooltip).clicked() {
+                        let preset_for_file = preset.clone();
+                        std::thread::spawn(move || {
+                            if let Some(path) = crate::gui::utils::pick_audio_file() {
+                                crate::api::audio::transcribe_audio_file(preset_for_file, path);
+                            }
+                        });
+                    }
+                });
             });
         }
 
@@ -295,6 +450,179 @@ pub fn render_preset_editor(
             }
         });
 
+        // Whisper-only request options
+        let is_whisper = get_model_by_id(&preset.model).is_some_and(|m| m.provider == "groq");
+        if is_audio && is_whisper {
+            ui.group(|ui| {
+                ui.label(egui::RichText::new(text.whisper_section).strong());
+                ui.horizontal(|ui| {
+                    ui.label(text.whisper_language_label);
+                    if ui.add(egui::TextEdit::singleline(&mut preset.whisper_language).hint_text("auto").desired_width(50.0)).changed() {
+                        changed = true;
+                    }
+                    ui.label(text.whisper_temperature_label);
+                    if ui.add(egui::Slider::new(&mut preset.whisper_temperature, 0.0..=1.0)).changed() {
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(text.whisper_prompt_label);
+                    if ui.add(egui::TextEdit::singleline(&mut preset.whisper_prompt).hint_text(text.whisper_prompt_hint).desired_width(260.0)).changed() {
+                        changed = true;
+                    }
+                });
+            });
+        }
+
+        // Offline OCR: no prompt, optional recognition language. Cloud models can fall back to it.
+        let is_local = get_model_by_id(&preset.model).is_some_and(|m| m.provider == "local");
+        if is_image && !is_local && preset.prompt_mode != "dynamic" {
+            if ui.checkbox(&mut preset.local_ocr_fallback, text.local_ocr_fallback_label).on_hover_text(text.local_ocr_fallback_tooltip).clicked() {
+                changed = true;
+            }
+        }
+        if is_image && (is_local || preset.local_ocr_fallback) {
+            ui.horizontal(|ui| {
+                ui.label(text.ocr_language_label);
+                if ui.add(egui::TextEdit::singleline(&mut preset.ocr_language).hint_text(text.ocr_language_hint).desired_width(80.0)).changed() {
+                    changed = true;
+                }
+            });
+            if is_local {
+                ui.label(egui::RichText::new(text.local_ocr_note).size(11.0).color(egui::Color32::GRAY));
+            }
+        }
+
+        // In-place ("screen translation") mode: positioned blocks instead of one window
+        if is_image && preset.prompt_mode != "dynamic" {
+            if ui.checkbox(&mut preset.overlay_blocks, text.overlay_blocks_label).on_hover_text(text.overlay_blocks_tooltip).clicked() {
+                changed = true;
+            }
+        }
+        if (is_image && preset.prompt_mode != "dynamic") || (is_audio && !preset.live_transcribe) {
+            if ui.checkbox(&mut preset.cache_results, text.cache_results_label).on_hover_text(text.cache_results_tooltip).clicked() {
+                changed = true;
+            }
+        }
+
+        // Structured output: user-defined JSON keys, one of them shown/copied
+        if is_image && preset.prompt_mode != "dynamic" && !preset.overlay_blocks {
+            ui.group(|ui| {
+                if ui.checkbox(&mut preset.structured_output, text.structured_output_label).on_hover_text(text.structured_output_tooltip).clicked() {
+                    if preset.structured_output && preset.output_fields.is_empty() {
+                        preset.output_fields.push(OutputField { name: "translation".to_string(), description: String::new() });
+                        preset.output_display_field = "translation".to_string();
+                    }
+                    changed = true;
+                }
+                if preset.structured_output {
+                    let mut remove_idx = None;
+                    for (i, field) in preset.output_fields.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.radio(preset.output_display_field == field.name, "").on_hover_text(text.output_display_field_tooltip).clicked() {
+                                preset.output_display_field = field.name.clone();
+                                changed = true;
+                            }
+                            let was_display = preset.output_display_field == field.name;
+                            if ui.add(egui::TextEdit::singleline(&mut field.name).hint_text(text.output_field_name_hint).desired_width(110.0)).changed() {
+                                if was_display {
+                                    preset.output_display_field = field.name.clone();
+                                }
+                                changed = true;
+                            }
+                            if ui.add(egui::TextEdit::singleline(&mut field.description).hint_text(text.output_field_desc_hint).desired_width(200.0)).changed() {
+                                changed = true;
+                            }
+                            if icon_button(ui, Icon::Close).clicked() {
+                                remove_idx = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove_idx {
+                        preset.output_fields.remove(i);
+                        changed = true;
+                    }
+                    if ui.button(text.output_add_field_btn).clicked() {
+                        preset.output_fields.push(OutputField::default());
+                        changed = true;
+                    }
+                }
+            });
+        }
+
+        // Image preprocessing before upload
+        if is_image {
+            ui.group(|ui| {
+                ui.label(egui::RichText::new(text.image_prep_section).strong());
+                ui.horizontal(|ui| {
+                    ui.label(text.image_max_dimension_label);
+                    if ui.add(egui::DragValue::new(&mut preset.image_max_dimension).clamp_range(0..=8192).speed(16.0)).on_hover_text(text.image_zero_off_tooltip).changed() {
+                        changed = true;
+                    }
+                    ui.label(text.image_upscale_below_label);
+                    if ui.add(egui::DragValue::new(&mut preset.image_upscale_below).clamp_range(0..=2048).speed(8.0)).on_hover_text(text.image_zero_off_tooltip).changed() {
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut preset.image_grayscale, text.image_grayscale_label).clicked() {
+                        changed = true;
+                    }
+                    if ui.checkbox(&mut preset.image_sharpen, text.image_sharpen_label).clicked() {
+                        changed = true;
+                    }
+                    ui.label(text.image_threshold_label);
+                    if ui.add(egui::Slider::new(&mut preset.image_threshold, 0..=254)).on_hover_text(text.image_zero_off_tooltip).changed() {
+                        changed = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(text.image_format_label);
+                    // WebP is encoded lossless, so only JPEG has a quality setting
+                    for (value, label) in [("png", "PNG"), ("jpeg", "JPEG"), ("webp", "WebP (lossless)")] {
+                        if ui.radio_value(&mut preset.image_format, value.to_string(), label).clicked() {
+                            changed = true;
+                        }
+                    }
+                    if preset.image_format == "jpeg" {
+                        ui.label(text.image_quality_label);
+                        if ui.add(egui::Slider::new(&mut preset.image_quality, 1..=100)).changed() {
+                            changed = true;
+                        }
+                    }
+                });
+            });
+        }
+
+        // Speaker diarization needs a Gemini audio model
+        let is_gemini = get_model_by_id(&preset.model).is_some_and(|m| m.provider == "google");
+        if is_audio && is_gemini {
+            if ui.checkbox(&mut preset.diarization, text.diarization_label).on_hover_text(text.diarization_tooltip).clicked() {
+                changed = true;
+            }
+        }
+
+        // Timestamps (Whisper segments / [mm:ss] lines from Gemini) and subtitle files
+        if is_audio {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut preset.timestamps, text.segment_timestamps_label).clicked() {
+                    if !preset.timestamps {
+                        preset.subtitle_format = "none".to_string();
+                    }
+                    changed = true;
+                }
+                ui.label(text.subtitle_format_label);
+                for (value, label) in [("none", text.subtitle_format_none), ("srt", "SRT"), ("vtt", "VTT")] {
+                    if ui.radio_value(&mut preset.subtitle_format, value.to_string(), label).clicked() {
+                        if value != "none" {
+                            preset.timestamps = true;
+                        }
+                        changed = true;
+                    }
+                }
+            });
+        }
+
         if !preset.hide_overlay {
             ui.group(|ui| {
                 ui.label(egui::RichText::new(text.retranslate_section).strong());
@@ -427,3 +755,33 @@ pub fn render_preset_editor(
     
     changed
 }
+
+// Device name picker; empty value = system default. A saved device that is not
+// connected right now stays selected and is shown as missing.
+fn device_combo(ui: &mut egui::Ui, id: (&str, usize), selected: &mut String, names: &[String], text: &LocaleText) -> bool {
+    let mut changed = false;
+    let current = if selected.is_empty() {
+        text.audio_device_default.to_string()
+    } else if names.contains(selected) {
+        selected.clone()
+    } else {
+        format!("{} ({})", selected, text.audio_device_missing)
+    };
+
+    egui::ComboBox::from_id_source(id)
+        .selected_text(current)
+        .width(260.0)
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(selected.is_empty(), text.audio_device_default).clicked() {
+                selected.clear();
+                changed = true;
+            }
+            for name in names {
+                if ui.selectable_label(selected == name, name).clicked() {
+                    *selected = name.clone();
+                    changed = true;
+                }
+            }
+        });
+    changed
+}
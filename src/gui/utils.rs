@@ -40,6 +40,32 @@ pub fn copy_to_clipboard_text(text: &str) {
     crate::overlay::utils::copy_to_clipboard(text, HWND(0));
 }
 
+// --- Audio File Picker ---
+// Native open dialog limited to the formats crate::audio::decode understands
+pub fn pick_audio_file() -> Option<std::path::PathBuf> {
+    use windows::Win32::UI::Controls::Dialogs::{GetOpenFileNameW, OPENFILENAMEW, OFN_FILEMUSTEXIST, OFN_PATHMUSTEXIST, OFN_NOCHANGEDIR};
+    use windows::core::PWSTR;
+
+    let filter: Vec<u16> = "Audio (*.wav, *.flac, *.mp3, *.ogg)\0*.wav;*.flac;*.mp3;*.ogg\0\0".encode_utf16().collect();
+    let mut file_buf = vec![0u16; 1024];
+    let mut ofn = OPENFILENAMEW {
+        lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
+        lpstrFilter: PCWSTR(filter.as_ptr()),
+        lpstrFile: PWSTR(file_buf.as_mut_ptr()),
+        nMaxFile: file_buf.len() as u32,
+        Flags: OFN_FILEMUSTEXIST | OFN_PATHMUSTEXIST | OFN_NOCHANGEDIR,
+        ..Default::default()
+    };
+
+    unsafe {
+        if !GetOpenFileNameW(&mut ofn).as_bool() {
+            return None;
+        }
+    }
+    let len = file_buf.iter().position(|&c| c == 0).unwrap_or(file_buf.len());
+    Some(std::path::PathBuf::from(String::from_utf16_lossy(&file_buf[..len])))
+}
+
 // --- Admin Check (Existing Code) ---
 
 #[cfg(target_os = "windows")]
@@ -6,6 +6,8 @@ use std::thread;
 use image::{ImageBuffer, Rgba};
 use std::fs;
 use serde::{Serialize, Deserialize};
+use crate::api::{ChatTurn, TranscriptSegment};
+use crate::audio::encode::AudioFormat;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum HistoryType {
@@ -20,11 +22,34 @@ pub struct HistoryItem {
     pub item_type: HistoryType,
     pub text: String,
     pub media_path: String,
+    // Re-run results point at the entry whose media they were generated from
+    #[serde(default)]
+    pub parent_id: Option<i64>,
+    #[serde(default)]
+    pub model_id: String,
+    // Follow-up refinements made in the result window (original result first)
+    #[serde(default)]
+    pub conversation: Vec<ChatTurn>,
+    // Whisper segment timestamps (audio entries only)
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    // Retranslation of an audio result and its target language (used for translated subtitles)
+    #[serde(default)]
+    pub translation: String,
+    #[serde(default)]
+    pub translation_lang: String,
+    // Preprocessed image as uploaded to the vision API (only when it differs from the capture)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub processed_media_path: String,
 }
 
 pub enum HistoryAction {
-    SaveImage { img: ImageBuffer<Rgba<u8>, Vec<u8>>, text: String },
-    SaveAudio { wav_data: Vec<u8>, text: String },
+    SaveImage { id: i64, img: ImageBuffer<Rgba<u8>, Vec<u8>>, text: String, processed: Option<(Vec<u8>, &'static str)> },
+    SaveAudio { id: i64, audio_data: Vec<u8>, text: String, segments: Vec<TranscriptSegment> },
+    SaveRerun { id: i64, parent_id: i64, text: String, model_id: String, segments: Vec<TranscriptSegment> },
+    UpdateConversation { id: i64, conversation: Vec<ChatTurn> },
+    SetTranslation { id: i64, text: String, lang: String },
+    UpdateText { id: i64, text: String },
     Delete(i64),
     ClearAll,
     Prune(usize),
@@ -59,20 +84,43 @@ impl HistoryManager {
         Self { tx, items }
     }
 
-    pub fn save_image(&self, img: ImageBuffer<Rgba<u8>, Vec<u8>>, text: String) {
-        let _ = self.tx.send(HistoryAction::SaveImage { img, text });
+    // Save functions return the id the entry will be stored under
+    // `processed`: uploaded bytes and their extension, stored next to the capture for debugging
+    pub fn save_image(&self, img: ImageBuffer<Rgba<u8>, Vec<u8>>, text: String, processed: Option<(Vec<u8>, &'static str)>) -> i64 {
+        let id = next_id();
+        let _ = self.tx.send(HistoryAction::SaveImage { id, img, text, processed });
+        id
     }
 
-    pub fn save_audio(&self, wav_data: Vec<u8>, text: String) {
-        let _ = self.tx.send(HistoryAction::SaveAudio { wav_data, text });
+    pub fn save_audio(&self, audio_data: Vec<u8>, text: String, segments: Vec<TranscriptSegment>) -> i64 {
+        let id = next_id();
+        let _ = self.tx.send(HistoryAction::SaveAudio { id, audio_data, text, segments });
+        id
+    }
+
+    pub fn save_rerun(&self, parent_id: i64, text: String, model_id: String, segments: Vec<TranscriptSegment>) -> i64 {
+        let id = next_id();
+        let _ = self.tx.send(HistoryAction::SaveRerun { id, parent_id, text, model_id, segments });
+        id
+    }
+
+    pub fn update_conversation(&self, id: i64, conversation: Vec<ChatTurn>) {
+        let _ = self.tx.send(HistoryAction::UpdateConversation { id, conversation });
+    }
+
+    pub fn set_translation(&self, id: i64, text: String, lang: String) {
+        let _ = self.tx.send(HistoryAction::SetTranslation { id, text, lang });
+    }
+
+    // Replaces the result text of an entry (watch mode grows one entry per session)
+    pub fn update_text(&self, id: i64, text: String) {
+        let _ = self.tx.send(HistoryAction::UpdateText { id, text });
     }
 
     pub fn delete(&self, id: i64) {
         let _ = self.tx.send(HistoryAction::Delete(id));
         let mut guard = self.items.lock().unwrap();
-        if let Some(pos) = guard.iter().position(|x| x.id == id) {
-            guard.remove(pos);
-        }
+        guard.retain(|x| x.id != id && x.parent_id != Some(id));
     }
 
     pub fn clear_all(&self) {
@@ -86,6 +134,10 @@ impl HistoryManager {
     }
 }
 
+fn next_id() -> i64 {
+    Local::now().timestamp_nanos_opt().unwrap_or(0)
+}
+
 fn get_paths() -> (PathBuf, PathBuf, PathBuf) {
     let config_dir = dirs::config_dir().unwrap_or_default().join("screen-goated-toolbox");
     let media_dir = config_dir.join("history_media");
@@ -94,6 +146,50 @@ fn get_paths() -> (PathBuf, PathBuf, PathBuf) {
     (config_dir, db_path, media_dir)
 }
 
+/// Path of a stored media file inside `history_media`
+pub fn get_media_path(media_path: &str) -> PathBuf {
+    let (_, _, media_dir) = get_paths();
+    media_dir.join(media_path)
+}
+
+// Removes an entry together with its re-run children. The media file is only
+// deleted once no remaining entry references it.
+fn remove_item(items: &mut Vec<HistoryItem>, id: i64, media_dir: &PathBuf) -> bool {
+    let before = items.len();
+    let mut removed_media = Vec::new();
+    items.retain(|x| {
+        if x.id == id || x.parent_id == Some(id) {
+            removed_media.push(x.media_path.clone());
+            if !x.processed_media_path.is_empty() {
+                removed_media.push(x.processed_media_path.clone());
+            }
+            false
+        } else {
+            true
+        }
+    });
+    for media in removed_media {
+        if !items.iter().any(|x| x.media_path == media || x.processed_media_path == media) {
+            let _ = fs::remove_file(media_dir.join(media));
+        }
+    }
+    items.len() != before
+}
+
+fn prune_items(items: &mut Vec<HistoryItem>, max_items: usize, media_dir: &PathBuf) -> bool {
+    let mut changed = false;
+    while items.len() > max_items {
+        // Dropping the oldest entry takes its whole re-run group with it
+        let id = match items.last() {
+            Some(item) => item.parent_id.unwrap_or(item.id),
+            None => break,
+        };
+        remove_item(items, id, media_dir);
+        changed = true;
+    }
+    changed
+}
+
 fn save_db(items: &Vec<HistoryItem>) {
     let (_, db_path, _) = get_paths();
     if let Ok(file) = fs::File::create(db_path) {
@@ -113,46 +209,103 @@ fn process_queue(
         let mut items = cache.lock().unwrap();
 
         match action {
-            HistoryAction::SaveImage { img, text } => {
+            HistoryAction::SaveImage { id, img, text, processed } => {
                 let now = Local::now();
                 let timestamp = now.format("%Y-%m-%d %H:%M:%S").to_string();
                 let filename = format!("img_{}.png", now.format("%Y%m%d_%H%M%S_%f"));
                 let path = media_dir.join(&filename);
-                let id = now.timestamp_nanos_opt().unwrap_or(0);
                 
                 if img.save(&path).is_ok() {
+                    let processed_media_path = match processed {
+                        Some((data, ext)) => {
+                            let name = format!("img_{}_processed.{}", now.format("%Y%m%d_%H%M%S_%f"), ext);
+                            if fs::write(media_dir.join(&name), data).is_ok() { name } else { String::new() }
+                        },
+                        None => String::new(),
+                    };
                     items.insert(0, HistoryItem {
                         id,
                         timestamp,
                         item_type: HistoryType::Image,
                         text,
                         media_path: filename,
+                        parent_id: None,
+                        model_id: String::new(),
+                        conversation: Vec::new(),
+                        segments: Vec::new(),
+                        translation: String::new(),
+                        translation_lang: String::new(),
+                        processed_media_path,
                     });
                     should_save = true;
                 }
             },
-            HistoryAction::SaveAudio { wav_data, text } => {
+            HistoryAction::SaveAudio { id, audio_data, text, segments } => {
                 let now = Local::now();
                 let timestamp = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                let filename = format!("audio_{}.wav", now.format("%Y%m%d_%H%M%S_%f"));
+                let ext = AudioFormat::detect(&audio_data).extension();
+                let filename = format!("audio_{}.{}", now.format("%Y%m%d_%H%M%S_%f"), ext);
                 let path = media_dir.join(&filename);
-                let id = now.timestamp_nanos_opt().unwrap_or(0);
                 
-                if fs::write(&path, wav_data).is_ok() {
+                if fs::write(&path, audio_data).is_ok() {
                     items.insert(0, HistoryItem {
                         id,
                         timestamp,
                         item_type: HistoryType::Audio,
                         text,
                         media_path: filename,
+                        parent_id: None,
+                        model_id: String::new(),
+                        conversation: Vec::new(),
+                        segments,
+                        translation: String::new(),
+                        translation_lang: String::new(),
+                        processed_media_path: String::new(),
+                    });
+                    should_save = true;
+                }
+            },
+            HistoryAction::SaveRerun { id, parent_id, text, model_id, segments } => {
+                if let Some(parent) = items.iter().find(|x| x.id == parent_id).cloned() {
+                    let now = Local::now();
+                    items.insert(0, HistoryItem {
+                        id,
+                        timestamp: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        item_type: parent.item_type,
+                        text,
+                        media_path: parent.media_path,
+                        parent_id: Some(parent_id),
+                        model_id,
+                        conversation: Vec::new(),
+                        segments,
+                        translation: String::new(),
+                        translation_lang: String::new(),
+                        processed_media_path: String::new(),
                     });
                     should_save = true;
                 }
             },
+            HistoryAction::UpdateConversation { id, conversation } => {
+                if let Some(item) = items.iter_mut().find(|x| x.id == id) {
+                    item.conversation = conversation;
+                    should_save = true;
+                }
+            },
+            HistoryAction::SetTranslation { id, text, lang } => {
+                if let Some(item) = items.iter_mut().find(|x| x.id == id) {
+                    item.translation = text;
+                    item.translation_lang = lang;
+                    should_save = true;
+                }
+            },
+            HistoryAction::UpdateText { id, text } => {
+                if let Some(item) = items.iter_mut().find(|x| x.id == id) {
+                    item.text = text;
+                    should_save = true;
+                }
+            },
             HistoryAction::Delete(id) => {
-                if let Some(pos) = items.iter().position(|x| x.id == id) {
-                    let item = items.remove(pos);
-                    let _ = fs::remove_file(media_dir.join(item.media_path));
+                if remove_item(&mut items, id, &media_dir) {
                     should_save = true;
                 }
             },
@@ -167,24 +320,14 @@ fn process_queue(
             },
             HistoryAction::Prune(new_limit) => {
                 max_items = new_limit;
-                if items.len() > max_items {
-                    while items.len() > max_items {
-                        if let Some(item) = items.pop() {
-                             let _ = fs::remove_file(media_dir.join(item.media_path));
-                        }
-                    }
+                if prune_items(&mut items, max_items, &media_dir) {
                     should_save = true;
                 }
             }
         }
         
         // Handle pruning after saves
-        if items.len() > max_items {
-             while items.len() > max_items {
-                 if let Some(item) = items.pop() {
-                      let _ = fs::remove_file(media_dir.join(item.media_path));
-                 }
-             }
+        if prune_items(&mut items, max_items, &media_dir) {
              should_save = true;
         }
 
@@ -0,0 +1,265 @@
+use image::{imageops, ImageBuffer, Rgba, RgbaImage};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use std::io::Cursor;
+use anyhow::Result;
+use crate::config::Preset;
+
+// --- IMAGE PREPROCESSING ---
+// Per-preset steps applied to a capture before it is base64-encoded for the
+// vision API: downscale, upscale of tiny crops, grayscale/threshold, sharpen,
+// then the upload encoding.
+
+// Upscaling never goes beyond this factor, however small the crop
+const MAX_UPSCALE: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageEncoding {
+    Png,
+    Jpeg,
+    WebP, // Always lossless: the image crate's WebP encoder has no lossy mode
+}
+
+impl ImageEncoding {
+    pub fn from_preset(value: &str) -> Self {
+        match value {
+            "jpeg" => ImageEncoding::Jpeg,
+            "webp" => ImageEncoding::WebP,
+            _ => ImageEncoding::Png,
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageEncoding::Png => "image/png",
+            ImageEncoding::Jpeg => "image/jpeg",
+            ImageEncoding::WebP => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageEncoding::Png => "png",
+            ImageEncoding::Jpeg => "jpg",
+            ImageEncoding::WebP => "webp",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrepOptions {
+    pub max_dimension: u32, // 0 = keep size
+    pub upscale_below: u32, // Shorter side under this gets enlarged, 0 = off
+    pub grayscale: bool,
+    pub threshold: u8,      // Black/white cut-off (implies grayscale), 0 = off
+    pub sharpen: bool,
+    pub encoding: ImageEncoding,
+    pub quality: u8,        // JPEG only, ignored for PNG and lossless WebP
+}
+
+impl PrepOptions {
+    pub fn from_preset(preset: &Preset) -> Self {
+        Self {
+            max_dimension: preset.image_max_dimension,
+            upscale_below: preset.image_upscale_below,
+            grayscale: preset.image_grayscale,
+            threshold: preset.image_threshold,
+            sharpen: preset.image_sharpen,
+            encoding: ImageEncoding::from_preset(&preset.image_format),
+            quality: preset.image_quality.clamp(1, 100),
+        }
+    }
+
+    // True when the upload is the untouched PNG of the capture
+    pub fn is_passthrough(&self) -> bool {
+        self.max_dimension == 0 && self.upscale_below == 0 && !self.grayscale
+            && self.threshold == 0 && !self.sharpen && self.encoding == ImageEncoding::Png
+    }
+}
+
+impl Default for PrepOptions {
+    fn default() -> Self {
+        Self {
+            max_dimension: 0,
+            upscale_below: 0,
+            grayscale: false,
+            threshold: 0,
+            sharpen: false,
+            encoding: ImageEncoding::Png,
+            quality: 85,
+        }
+    }
+}
+
+// Encoded upload, the exact bytes sent to the API
+pub struct PreparedImage {
+    pub data: Vec<u8>,
+    pub encoding: ImageEncoding,
+}
+
+impl PreparedImage {
+    pub fn mime_type(&self) -> &'static str {
+        self.encoding.mime_type()
+    }
+}
+
+// Target size after the max-dimension / tiny-text rules, None when unchanged
+fn target_size(width: u32, height: u32, opts: &PrepOptions) -> Option<(u32, u32)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let longer = width.max(height) as f32;
+    let shorter = width.min(height) as f32;
+
+    let mut scale = 1.0f32;
+    if opts.upscale_below > 0 && shorter < opts.upscale_below as f32 {
+        scale = (opts.upscale_below as f32 / shorter).min(MAX_UPSCALE);
+    }
+    if opts.max_dimension > 0 && longer * scale > opts.max_dimension as f32 {
+        scale = opts.max_dimension as f32 / longer;
+    }
+    if (scale - 1.0).abs() < 0.01 {
+        return None;
+    }
+    let w = ((width as f32 * scale).round() as u32).max(1);
+    let h = ((height as f32 * scale).round() as u32).max(1);
+    Some((w, h))
+}
+
+pub fn process(img: &RgbaImage, opts: &PrepOptions) -> RgbaImage {
+    let mut out = match target_size(img.width(), img.height(), opts) {
+        // Catmull-Rom keeps glyph edges crisp when enlarging; Lanczos for shrinking
+        Some((w, h)) if w > img.width() => imageops::resize(img, w, h, FilterType::CatmullRom),
+        Some((w, h)) => imageops::resize(img, w, h, FilterType::Lanczos3),
+        None => img.clone(),
+    };
+
+    if opts.sharpen {
+        out = imageops::unsharpen(&out, 1.0, 4);
+    }
+
+    if opts.grayscale || opts.threshold > 0 {
+        for px in out.pixels_mut() {
+            let [r, g, b, a] = px.0;
+            // Rec. 601 luma
+            let mut luma = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+            if opts.threshold > 0 {
+                luma = if luma >= opts.threshold { 255 } else { 0 };
+            }
+            *px = Rgba([luma, luma, luma, a]);
+        }
+    }
+    out
+}
+
+pub fn encode(img: &RgbaImage, encoding: ImageEncoding, quality: u8) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    match encoding {
+        ImageEncoding::Png => {
+            img.write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)?;
+        },
+        ImageEncoding::Jpeg => {
+            // JPEG has no alpha channel
+            let rgb = image::DynamicImage::ImageRgba8(img.clone()).to_rgb8();
+            JpegEncoder::new_with_quality(&mut data, quality).encode(rgb.as_raw(), rgb.width(), rgb.height(), image::ColorType::Rgb8)?;
+        },
+        ImageEncoding::WebP => {
+            WebPEncoder::new_lossless(&mut data).encode(img.as_raw(), img.width(), img.height(), image::ColorType::Rgba8)?;
+        },
+    }
+    Ok(data)
+}
+
+// `original_png` is the capture already encoded for the refine context; it is
+// reused as-is when the preset does no preprocessing or the pipeline fails.
+pub fn prepare(img: &ImageBuffer<Rgba<u8>, Vec<u8>>, original_png: &[u8], opts: &PrepOptions) -> PreparedImage {
+    let original = || PreparedImage { data: original_png.to_vec(), encoding: ImageEncoding::Png };
+    if opts.is_passthrough() {
+        return original();
+    }
+    match encode(&process(img, opts), opts.encoding, opts.quality) {
+        Ok(data) => PreparedImage { data, encoding: opts.encoding },
+        Err(e) => {
+            eprintln!("Image preprocessing failed, sending the original capture: {}", e);
+            original()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker(width: u32, height: u32) -> RgbaImage {
+        ImageBuffer::from_fn(width, height, |x, y| {
+            if (x / 4 + y / 4) % 2 == 0 { Rgba([200, 40, 40, 255]) } else { Rgba([20, 20, 220, 128]) }
+        })
+    }
+
+    fn opts(max_dimension: u32, upscale_below: u32) -> PrepOptions {
+        PrepOptions { max_dimension, upscale_below, ..PrepOptions::default() }
+    }
+
+    #[test]
+    fn downscale_keeps_aspect_ratio() {
+        assert_eq!(target_size(4000, 1000, &opts(2000, 0)), Some((2000, 500)));
+        assert_eq!(target_size(1000, 4000, &opts(2000, 0)), Some((500, 2000)));
+        assert_eq!(target_size(1500, 800, &opts(2000, 0)), None);
+        let out = process(&checker(400, 100), &opts(200, 0));
+        assert_eq!(out.dimensions(), (200, 50));
+    }
+
+    #[test]
+    fn upscale_small_crops_up_to_the_cap() {
+        assert_eq!(target_size(120, 20, &opts(0, 40)), Some((240, 40)));
+        // Never more than MAX_UPSCALE, however tiny the crop
+        assert_eq!(target_size(50, 5, &opts(0, 100)), Some((200, 20)));
+        // The max dimension still wins over the upscale
+        assert_eq!(target_size(300, 20, &opts(400, 40)), Some((400, 27)));
+        let out = process(&checker(30, 10), &opts(0, 20));
+        assert_eq!(out.dimensions(), (60, 20));
+    }
+
+    #[test]
+    fn grayscale_and_threshold() {
+        let img = checker(8, 8);
+        let gray = process(&img, &PrepOptions { grayscale: true, ..PrepOptions::default() });
+        assert_eq!(gray.dimensions(), (8, 8));
+        // 0.299 * 200 + 0.587 * 40 + 0.114 * 40, alpha kept
+        assert_eq!(gray.get_pixel(0, 0).0, [87, 87, 87, 255]);
+        assert_eq!(gray.get_pixel(4, 0).0, [42, 42, 42, 128]);
+
+        let bw = process(&img, &PrepOptions { threshold: 60, ..PrepOptions::default() });
+        assert_eq!(bw.get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(bw.get_pixel(4, 0).0, [0, 0, 0, 128]);
+        assert!(bw.pixels().all(|p| p.0[0] == 0 || p.0[0] == 255));
+    }
+
+    #[test]
+    fn encodings_match_their_mime_type() {
+        let img = checker(16, 12);
+        for encoding in [ImageEncoding::Png, ImageEncoding::Jpeg, ImageEncoding::WebP] {
+            let data = encode(&img, encoding, 80).unwrap();
+            let format = image::guess_format(&data).unwrap();
+            assert_eq!(format.to_mime_type(), encoding.mime_type());
+            let decoded = image::load_from_memory(&data).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (16, 12));
+        }
+        // Lossless WebP round-trips exactly
+        let webp = encode(&img, ImageEncoding::WebP, 10).unwrap();
+        assert_eq!(image::load_from_memory(&webp).unwrap().to_rgba8(), img);
+    }
+
+    #[test]
+    fn passthrough_reuses_the_original_png() {
+        let img = checker(4, 4);
+        let prepared = prepare(&img, b"original", &PrepOptions::default());
+        assert_eq!(prepared.data, b"original");
+        assert_eq!(prepared.mime_type(), "image/png");
+
+        let jpeg = prepare(&img, b"original", &PrepOptions { encoding: ImageEncoding::Jpeg, ..PrepOptions::default() });
+        assert_eq!(jpeg.mime_type(), "image/jpeg");
+        assert_ne!(jpeg.data, b"original");
+    }
+}
@@ -0,0 +1,201 @@
+use image::{imageops, RgbaImage};
+
+// --- SCROLL STITCHING ---
+// Successive captures of the same region taken while it scrolls are joined by
+// finding how far the content moved between two frames; the stitched image is
+// cut back into tiles the vision models can read at full resolution. Separate
+// regions of a multi-region selection are simply stacked.
+
+const SIG_COLUMNS: u32 = 32; // Row signature: average luma of this many column blocks
+const MIN_OVERLAP: u32 = 24; // Rows two frames must share for a match to count
+const MATCH_TOLERANCE: f32 = 4.0; // Mean luma difference still accepted as the same content
+pub const MAX_STITCHED_HEIGHT: u32 = 20000;
+const REGION_GAP: u32 = 16; // Gray band between stacked regions
+
+fn luma(px: &image::Rgba<u8>) -> u32 {
+    (px[0] as u32 * 299 + px[1] as u32 * 587 + px[2] as u32 * 114) / 1000
+}
+
+fn row_signatures(img: &RgbaImage) -> Vec<Vec<u32>> {
+    let (w, h) = img.dimensions();
+    let cols = SIG_COLUMNS.min(w).max(1);
+    (0..h).map(|y| {
+        (0..cols).map(|c| {
+            let x0 = c * w / cols;
+            let x1 = ((c + 1) * w / cols).max(x0 + 1);
+            let sum: u32 = (x0..x1).map(|x| luma(img.get_pixel(x, y))).sum();
+            sum / (x1 - x0)
+        }).collect()
+    }).collect()
+}
+
+// Mean difference between prev shifted up by `dy` and curr (every other row)
+fn shift_error(prev: &[Vec<u32>], curr: &[Vec<u32>], dy: usize) -> f32 {
+    let overlap = prev.len() - dy;
+    let mut total = 0u64;
+    let mut count = 0u64;
+    for r in (0..overlap).step_by(2) {
+        for (a, b) in prev[r + dy].iter().zip(&curr[r]) {
+            total += a.abs_diff(*b) as u64;
+            count += 1;
+        }
+    }
+    if count == 0 { f32::MAX } else { total as f32 / count as f32 }
+}
+
+// How many pixel rows the content scrolled up from `prev` to `curr`.
+// Some(0) = nothing moved (end of the page), None = no overlap found.
+pub fn find_scroll_offset(prev: &RgbaImage, curr: &RgbaImage) -> Option<u32> {
+    if prev.dimensions() != curr.dimensions() || prev.height() <= MIN_OVERLAP {
+        return None;
+    }
+    let prev_sig = row_signatures(prev);
+    let curr_sig = row_signatures(curr);
+
+    if shift_error(&prev_sig, &curr_sig, 0) <= MATCH_TOLERANCE / 4.0 {
+        return Some(0);
+    }
+
+    let max_dy = (prev.height() - MIN_OVERLAP) as usize;
+    let (best_dy, best_err) = (1..=max_dy)
+        .map(|dy| (dy, shift_error(&prev_sig, &curr_sig, dy)))
+        .fold((0, f32::MAX), |best, cand| if cand.1 < best.1 { cand } else { best });
+
+    (best_err <= MATCH_TOLERANCE).then_some(best_dy as u32)
+}
+
+// Appends the `dy` new rows at the bottom of `curr` to the stitched image
+pub fn append_scrolled(stitched: RgbaImage, curr: &RgbaImage, dy: u32) -> RgbaImage {
+    let (w, h) = stitched.dimensions();
+    let dy = dy.min(curr.height());
+    let row_bytes = (w * 4) as usize;
+    let start = (curr.height() - dy) as usize * row_bytes;
+
+    let mut raw = stitched.into_raw();
+    raw.extend_from_slice(&curr.as_raw()[start..]);
+    RgbaImage::from_raw(w, h + dy, raw).expect("stitched buffer size")
+}
+
+// Luma range of a row; text rows are busy, the gaps between lines are flat
+fn row_activity(img: &RgbaImage, y: u32) -> u32 {
+    let (min, max) = (0..img.width()).map(|x| luma(img.get_pixel(x, y)))
+        .fold((u32::MAX, 0), |(lo, hi), l| (lo.min(l), hi.max(l)));
+    max.saturating_sub(min)
+}
+
+// Splits a tall image into tiles of at most `tile_height` rows, cutting at the
+// quietest row of each tile's last fifth so no text line is cut in half.
+// Empty when the image already fits in one tile.
+pub fn split_tiles(img: &RgbaImage, tile_height: u32) -> Vec<RgbaImage> {
+    let h = img.height();
+    if h <= tile_height {
+        return Vec::new();
+    }
+
+    let mut tiles = Vec::new();
+    let mut start = 0;
+    while start < h {
+        let mut end = (start + tile_height).min(h);
+        if end < h {
+            let search_from = end - tile_height / 5;
+            end = (search_from..end).rev()
+                .min_by_key(|&y| row_activity(img, y))
+                .unwrap_or(end)
+                .max(start + 1);
+        }
+        tiles.push(imageops::crop_imm(img, 0, start, img.width(), end - start).to_image());
+        start = end;
+    }
+    tiles
+}
+
+// Stacks separate captures top to bottom, left-aligned, with a gray band between
+// them so the model reads them as distinct regions
+pub fn stack_regions(crops: &[RgbaImage]) -> RgbaImage {
+    let width = crops.iter().map(|c| c.width()).max().unwrap_or(1);
+    let height = crops.iter().map(|c| c.height()).sum::<u32>() + REGION_GAP * crops.len().saturating_sub(1) as u32;
+    let mut out = RgbaImage::from_pixel(width, height.max(1), image::Rgba([128, 128, 128, 255]));
+
+    let mut y = 0;
+    for crop in crops {
+        imageops::replace(&mut out, crop, 0, y as i64);
+        y += crop.height() + REGION_GAP;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    // Tall "page" whose rows all look different, so any shift is unambiguous
+    fn page(width: u32, height: u32, seed: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let mut h = (y + seed).wrapping_mul(0x9e3779b9);
+            h ^= h >> 15;
+            h = h.wrapping_mul(0x85ebca6b);
+            h ^= h >> 13;
+            let v = (h as u8).wrapping_add((x / 8 * 40) as u8);
+            Rgba([v, v.wrapping_add(60), v, 255])
+        })
+    }
+
+    fn view(img: &RgbaImage, top: u32, height: u32) -> RgbaImage {
+        imageops::crop_imm(img, 0, top, img.width(), height).to_image()
+    }
+
+    #[test]
+    fn offset_of_a_known_scroll() {
+        let doc = page(64, 400, 1);
+        let prev = view(&doc, 0, 120);
+        assert_eq!(find_scroll_offset(&prev, &view(&doc, 37, 120)), Some(37));
+        assert_eq!(find_scroll_offset(&prev, &view(&doc, 90, 120)), Some(90));
+        // Nothing moved: end of the page
+        assert_eq!(find_scroll_offset(&prev, &prev.clone()), Some(0));
+    }
+
+    #[test]
+    fn no_offset_without_overlap() {
+        let doc = page(64, 400, 1);
+        let prev = view(&doc, 0, 120);
+        // Scrolled further than one frame, or a different page altogether
+        assert_eq!(find_scroll_offset(&prev, &view(&doc, 200, 120)), None);
+        assert_eq!(find_scroll_offset(&prev, &view(&page(64, 400, 999), 0, 120)), None);
+        assert_eq!(find_scroll_offset(&prev, &view(&doc, 0, 100)), None);
+    }
+
+    #[test]
+    fn stitched_frames_rebuild_the_page() {
+        let doc = page(48, 300, 7);
+        let mut stitched = view(&doc, 0, 100);
+        let mut prev = stitched.clone();
+        for top in [30, 75, 140, 200] {
+            let frame = view(&doc, top, 100);
+            let dy = find_scroll_offset(&prev, &frame).unwrap();
+            stitched = append_scrolled(stitched, &frame, dy);
+            prev = frame;
+        }
+        assert_eq!(stitched.dimensions(), (48, 300));
+        assert_eq!(stitched, doc);
+    }
+
+    #[test]
+    fn tiles_cut_between_lines() {
+        // 20 busy "text" rows, then 10 flat rows, repeated
+        let img = RgbaImage::from_fn(40, 1000, |x, y| {
+            if y % 30 < 20 && x % 2 == 0 { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) }
+        });
+        assert!(split_tiles(&img, 1000).is_empty());
+
+        let tiles = split_tiles(&img, 300);
+        assert!(tiles.len() >= 4);
+        assert_eq!(tiles.iter().map(|t| t.height()).sum::<u32>(), 1000);
+        let mut y = 0;
+        for tile in &tiles[..tiles.len() - 1] {
+            assert!(tile.height() <= 300);
+            y += tile.height();
+            assert!(y % 30 >= 20, "cut at row {} is inside a text line", y);
+        }
+    }
+}
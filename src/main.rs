@@ -2,12 +2,16 @@
 
 mod config;
 mod api;
+mod audio;
 mod gui;
 mod overlay;
 mod icon_gen;
 mod model_config;
 mod updater;
 mod history;
+mod subtitle;
+mod imaging;
+mod result_cache;
 
 use std::sync::{Arc, Mutex};
 use std::panic;
@@ -15,7 +19,6 @@ use windows::Win32::UI::WindowsAndMessaging::*;
 use windows::Win32::UI::Input::KeyboardAndMouse::*;
 use windows::Win32::System::LibraryLoader::*;
 use windows::Win32::Foundation::*;
-use windows::Win32::Graphics::Gdi::*;
 use windows::Win32::System::Threading::*;
 use windows::Win32::System::Com::CoInitialize;
 use windows::core::*;
@@ -172,8 +175,10 @@ fn main() -> eframe::Result<()> {
     // --- TRAY MENU SETUP ---
     let tray_menu = Menu::new();
     let settings_i = MenuItem::with_id("1002", "Settings", true, None);
+    let transcribe_file_i = MenuItem::with_id("1003", "Transcribe Audio File...", true, None);
     let quit_i = MenuItem::with_id("1001", "Quit", true, None);
     let _ = tray_menu.append(&settings_i);
+    let _ = tray_menu.append(&transcribe_file_i);
     let _ = tray_menu.append(&quit_i);
 
     // --- WINDOW SETUP ---
@@ -233,13 +238,23 @@ fn register_all_hotkeys(hwnd: HWND) {
     
     let mut registered_ids = Vec::new();
     for (p_idx, preset) in presets.iter().enumerate() {
+        // "Reuse last region" presets also get Shift+hotkey to select a new region
+        let reselect = preset.preset_type == "image" && preset.capture_mode == "last";
         for (h_idx, hotkey) in preset.hotkeys.iter().enumerate() {
-            // ID encoding: 1000 * preset_idx + hotkey_idx + 1
+            // ID encoding: 1000 * preset_idx + hotkey_idx + 1 (+ RESELECT_ID_OFFSET for the Shift variant)
             let id = (p_idx as i32 * 1000) + (h_idx as i32) + 1;
             unsafe {
                 RegisterHotKey(hwnd, id, HOT_KEY_MODIFIERS(hotkey.modifiers), hotkey.code);
             }
             registered_ids.push(id);
+
+            if reselect && hotkey.modifiers & MOD_SHIFT.0 == 0 {
+                let reselect_id = id + RESELECT_ID_OFFSET;
+                unsafe {
+                    RegisterHotKey(hwnd, reselect_id, HOT_KEY_MODIFIERS(hotkey.modifiers) | MOD_SHIFT, hotkey.code);
+                }
+                registered_ids.push(reselect_id);
+            }
         }
     }
     app.registered_hotkey_ids = registered_ids;
@@ -253,6 +268,8 @@ fn unregister_all_hotkeys(hwnd: HWND) {
 }
 
 const WM_RELOAD_HOTKEYS: u32 = WM_USER + 101;
+// Added to a hotkey ID for its "select a new region" Shift variant
+const RESELECT_ID_OFFSET: i32 = 500;
 
 fn run_hotkey_listener() {
     unsafe {
@@ -319,6 +336,7 @@ unsafe extern "system" fn hotkey_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
             let id = wparam.0 as i32;
             if id > 0 {
                 let preset_idx = ((id - 1) / 1000) as usize;
+                let force_select = (id - 1) % 1000 >= RESELECT_ID_OFFSET;
                 
                 // Determine context to decide if we should capture the window
                 let (preset_type, is_audio_stopping) = {
@@ -356,6 +374,10 @@ unsafe extern "system" fn hotkey_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
                     if overlay::is_selection_overlay_active_and_dismiss() {
                         return LRESULT(0);
                     }
+                    // The hotkey of a watching preset stops the watch
+                    if overlay::stop_watch(preset_idx) {
+                        return LRESULT(0);
+                    }
                     
                     let app_clone = APP.clone();
                     let p_idx = preset_idx;
@@ -363,6 +385,10 @@ unsafe extern "system" fn hotkey_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
                     std::thread::spawn(move || {
                         match capture_full_screen() {
                             Ok(img) => {
+                                // Saved regions, windows and monitors go straight to processing
+                                if !force_select && overlay::process_capture_target(p_idx, &img) {
+                                    return;
+                                }
                                 if let Ok(mut app) = app_clone.lock() {
                                     app.original_screenshot = Some(img);
                                 } else {
@@ -384,68 +410,13 @@ unsafe extern "system" fn hotkey_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
 }
 
 fn capture_full_screen() -> anyhow::Result<ImageBuffer<image::Rgba<u8>, Vec<u8>>> {
-    unsafe {
-        let x = GetSystemMetrics(SM_XVIRTUALSCREEN);
-        let y = GetSystemMetrics(SM_YVIRTUALSCREEN);
-        let width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
-        let height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
-        
-        // Validate dimensions
-        if width <= 0 || height <= 0 {
-            return Err(anyhow::anyhow!("GDI Error: Invalid screen dimensions ({} x {})", width, height));
-        }
-
-        let hdc_screen = GetDC(None);
-        if hdc_screen.0 == 0 {
-            return Err(anyhow::anyhow!("GDI Error: Failed to get screen device context"));
-        }
-        
-        let hdc_mem = CreateCompatibleDC(hdc_screen);
-        if hdc_mem.0 == 0 {
-            ReleaseDC(None, hdc_screen);
-            return Err(anyhow::anyhow!("GDI Error: Failed to create compatible device context"));
-        }
-        
-        let hbitmap = CreateCompatibleBitmap(hdc_screen, width, height);
-        
-        if hbitmap.0 == 0 {
-             DeleteDC(hdc_mem);
-             ReleaseDC(None, hdc_screen);
-             return Err(anyhow::anyhow!("GDI Error: Failed to create compatible bitmap."));
-        }
-        
-        SelectObject(hdc_mem, hbitmap);
-
-        BitBlt(hdc_mem, 0, 0, width, height, hdc_screen, x, y, SRCCOPY).ok()?;
-
-        let mut bmi = BITMAPINFO {
-            bmiHeader: BITMAPINFOHEADER {
-                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                biWidth: width,
-                biHeight: -height,
-                biPlanes: 1,
-                biBitCount: 32,
-                biCompression: BI_RGB.0 as u32,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-
-        let mut buffer: Vec<u8> = vec![0; (width * height * 4) as usize];
-        GetDIBits(hdc_mem, hbitmap, 0, height as u32, Some(buffer.as_mut_ptr() as *mut _), &mut bmi, DIB_RGB_COLORS);
-
-        for chunk in buffer.chunks_exact_mut(4) {
-            chunk.swap(0, 2);
-            chunk[3] = 255;
-        }
-
-        DeleteObject(hbitmap);
-        DeleteDC(hdc_mem);
-        ReleaseDC(None, hdc_screen);
-
-        let img = ImageBuffer::from_raw(width as u32, height as u32, buffer)
-            .ok_or_else(|| anyhow::anyhow!("Buffer creation failed"))?;
-        
-        Ok(img)
-    }
-}
\ No newline at end of file
+    let (x, y, width, height) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    overlay::capture_screen_rect(RECT { left: x, top: y, right: x + width, bottom: y + height })
+}
@@ -119,6 +119,19 @@ lazy_static::lazy_static! {
             "250 요청/일",
             "250 requests/day"
         ),
+        ModelConfig::new(
+            "local_ocr",
+            "local",
+            "Ngoại tuyến (OCR Windows)",
+            "오프라인 (Windows OCR)",
+            "Offline (Windows OCR)",
+            "windows-ocr",
+            ModelType::Vision,
+            true,
+            "Không giới hạn, không cần mạng",
+            "무제한, 네트워크 불필요",
+            "Unlimited, no network"
+        ),
         ModelConfig::new(
             "fast_text",
             "groq",
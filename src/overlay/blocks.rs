@@ -0,0 +1,195 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_ESCAPE;
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::System::LibraryLoader::*;
+use windows::core::*;
+use std::sync::{Mutex, Once};
+use std::collections::HashMap;
+
+use crate::api::types::OcrBlock;
+use super::utils::copy_to_clipboard;
+
+// --- IN-PLACE BLOCK OVERLAY ---
+// Screen translation result: one layered window over the captured region with
+// each translated block painted where its source text was. Everything outside
+// the blocks is color-keyed, so it stays visible and click-through: clicks only
+// reach the overlay on a block. Left click on a block closes the overlay, right
+// click copies it, and Esc closes it from anywhere.
+
+static REGISTER_BLOCKS_CLASS: Once = Once::new();
+
+// Transparent (keyed) background; COLORREF is 0x00BBGGRR
+const KEY_COLOR: u32 = 0x00FF00FF;
+const BLOCK_BG: u32 = 0x00202020;
+const BLOCK_TEXT: u32 = 0x00FFFFFF;
+const OVERLAY_ALPHA: u8 = 235;
+// Tiny boxes still get room for one readable line
+const MIN_BLOCK_W: i32 = 40;
+const MIN_BLOCK_H: i32 = 18;
+const MIN_FONT: i32 = 10;
+const MAX_FONT: i32 = 28;
+
+lazy_static::lazy_static! {
+    // Block boxes (client coordinates) and their text, by overlay window
+    static ref BLOCK_STATES: Mutex<HashMap<isize, Vec<(RECT, String)>>> = Mutex::new(HashMap::new());
+}
+
+// Opens the overlay on its own message-loop thread and returns its handle
+pub fn show_block_overlay(blocks: &[OcrBlock], capture_rect: RECT) -> HWND {
+    let w = capture_rect.right - capture_rect.left;
+    let h = capture_rect.bottom - capture_rect.top;
+    let boxes: Vec<(RECT, String)> = blocks.iter().map(|block| (block_rect(block, w, h), block.translation.clone())).collect();
+
+    let (tx_hwnd, rx_hwnd) = std::sync::mpsc::channel();
+    std::thread::spawn(move || unsafe {
+        let hwnd = create_block_window(capture_rect, boxes);
+        let _ = tx_hwnd.send(hwnd);
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).into() {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+            if !IsWindow(hwnd).as_bool() { break; }
+        }
+    });
+    rx_hwnd.recv().unwrap_or(HWND(0))
+}
+
+// Block box in overlay coordinates, grown to the minimum size but kept on the overlay
+fn block_rect(block: &OcrBlock, w: i32, h: i32) -> RECT {
+    let mut left = (block.left * w as f32) as i32;
+    let mut top = (block.top * h as f32) as i32;
+    let mut right = ((block.right * w as f32) as i32).max(left + MIN_BLOCK_W);
+    let mut bottom = ((block.bottom * h as f32) as i32).max(top + MIN_BLOCK_H);
+    if right > w {
+        left = (left - (right - w)).max(0);
+        right = w;
+    }
+    if bottom > h {
+        top = (top - (bottom - h)).max(0);
+        bottom = h;
+    }
+    RECT { left, top, right, bottom }
+}
+
+unsafe fn create_block_window(rect: RECT, boxes: Vec<(RECT, String)>) -> HWND {
+    let instance = GetModuleHandleW(None).unwrap();
+    let class_name = w!("SGTBlockOverlay");
+
+    REGISTER_BLOCKS_CLASS.call_once(|| {
+        let mut wc = WNDCLASSW::default();
+        wc.lpfnWndProc = Some(block_wnd_proc);
+        wc.hInstance = instance;
+        wc.hCursor = LoadCursorW(None, IDC_HAND).unwrap();
+        wc.lpszClassName = class_name;
+        wc.style = CS_HREDRAW | CS_VREDRAW;
+        wc.hbrBackground = HBRUSH(0);
+        RegisterClassW(&wc);
+    });
+
+    let hwnd = CreateWindowExW(
+        WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+        class_name,
+        w!("Translation"),
+        WS_POPUP,
+        rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top,
+        None, None, instance, None
+    );
+
+    BLOCK_STATES.lock().unwrap().insert(hwnd.0 as isize, boxes);
+    SetLayeredWindowAttributes(hwnd, COLORREF(KEY_COLOR), OVERLAY_ALPHA, LWA_COLORKEY | LWA_ALPHA);
+    ShowWindow(hwnd, SW_SHOW);
+    // Keyboard focus for Esc, clicks on the keyed area never activate it
+    SetForegroundWindow(hwnd);
+    hwnd
+}
+
+unsafe fn create_font(size: i32) -> HFONT {
+    CreateFontW(size, 0, 0, 0, FW_MEDIUM.0 as i32, 0, 0, 0, DEFAULT_CHARSET.0 as u32, OUT_DEFAULT_PRECIS.0 as u32, CLIP_DEFAULT_PRECIS.0 as u32, CLEARTYPE_QUALITY.0 as u32, (VARIABLE_PITCH.0 | FF_SWISS.0) as u32, w!("Segoe UI"))
+}
+
+// Largest font (down to MIN_FONT) whose wrapped text fits the box
+unsafe fn fit_font(hdc: HDC, text: &mut [u16], rect: RECT) -> HFONT {
+    let width = rect.right - rect.left;
+    let height = rect.bottom - rect.top;
+    let mut size = (height - 4).clamp(MIN_FONT, MAX_FONT);
+    loop {
+        let font = create_font(size);
+        let old = SelectObject(hdc, font);
+        let mut calc = RECT { left: 0, top: 0, right: width, bottom: 0 };
+        DrawTextW(hdc, text, &mut calc, DT_CALCRECT | DT_WORDBREAK | DT_EDITCONTROL);
+        SelectObject(hdc, old);
+        if size <= MIN_FONT || (calc.bottom <= height && calc.right <= width) {
+            return font;
+        }
+        DeleteObject(font);
+        size -= 1;
+    }
+}
+
+unsafe fn paint_blocks(hwnd: HWND, hdc: HDC) {
+    let mut client = RECT::default();
+    GetClientRect(hwnd, &mut client);
+    let key_brush = CreateSolidBrush(COLORREF(KEY_COLOR));
+    FillRect(hdc, &client, key_brush);
+    DeleteObject(key_brush);
+
+    let boxes = BLOCK_STATES.lock().unwrap().get(&(hwnd.0 as isize)).cloned().unwrap_or_default();
+    let bg_brush = CreateSolidBrush(COLORREF(BLOCK_BG));
+    SetBkMode(hdc, TRANSPARENT);
+    SetTextColor(hdc, COLORREF(BLOCK_TEXT));
+
+    for (rect, text) in boxes {
+        FillRect(hdc, &rect, bg_brush);
+        let mut text_w: Vec<u16> = text.encode_utf16().collect();
+        let mut text_rect = RECT { left: rect.left + 2, top: rect.top + 1, right: rect.right - 2, bottom: rect.bottom - 1 };
+        let font = fit_font(hdc, &mut text_w, text_rect);
+        let old = SelectObject(hdc, font);
+        DrawTextW(hdc, &mut text_w, &mut text_rect, DT_LEFT | DT_WORDBREAK | DT_EDITCONTROL);
+        SelectObject(hdc, old);
+        DeleteObject(font);
+    }
+    DeleteObject(bg_brush);
+}
+
+fn block_at(hwnd: HWND, lparam: LPARAM) -> Option<String> {
+    let x = (lparam.0 & 0xFFFF) as i16 as i32;
+    let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
+    let states = BLOCK_STATES.lock().unwrap();
+    states.get(&(hwnd.0 as isize))?.iter()
+        .find(|(r, _)| x >= r.left && x < r.right && y >= r.top && y < r.bottom)
+        .map(|(_, text)| text.clone())
+}
+
+unsafe extern "system" fn block_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            let mut ps = PAINTSTRUCT::default();
+            let hdc = BeginPaint(hwnd, &mut ps);
+            paint_blocks(hwnd, hdc);
+            EndPaint(hwnd, &ps);
+            LRESULT(0)
+        }
+        WM_LBUTTONUP => {
+            DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_RBUTTONUP => {
+            if let Some(text) = block_at(hwnd, lparam) {
+                copy_to_clipboard(&text, hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_KEYDOWN if wparam.0 == VK_ESCAPE.0 as usize => {
+            DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            BLOCK_STATES.lock().unwrap().remove(&(hwnd.0 as isize));
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
@@ -1,5 +1,9 @@
 pub mod utils;
 mod selection;
+mod region;
+mod scroll;
+mod watch;
+mod blocks;
 pub mod result;
 pub mod recording; 
 pub mod process;
@@ -7,4 +11,6 @@ pub mod broom_assets;
 pub mod paint_utils;
 
 pub use selection::{show_selection_overlay, is_selection_overlay_active_and_dismiss};
+pub use region::{process_capture_target, capture_screen_rect};
+pub use watch::stop_watch;
 pub use recording::{show_recording_overlay, is_recording_overlay_active, stop_recording_and_submit};
@@ -7,10 +7,19 @@ use std::sync::{Arc, Mutex, Once};
 use std::collections::HashMap;
 use image::{ImageBuffer, Rgba};
 
-use crate::api::{translate_image_streaming, translate_text_streaming};
+use crate::api::{translate_image_streaming, translate_text_streaming, Transcript};
+use crate::api::types::{parse_ocr_blocks, blocks_text};
+use crate::api::vision::OCR_BLOCKS_INSTRUCTION;
+use crate::api::schema::OutputSchema;
+use crate::api::ocr::{recognize_image_streaming, recognize_with_backend, OcrBackend};
 use crate::config::{Config, Preset};
+use crate::history::{HistoryItem, HistoryType};
+use crate::subtitle::SubtitleFormat;
+use crate::imaging::preprocess::{prepare as prepare_image, PrepOptions, PreparedImage};
+use crate::imaging::stitch::split_tiles;
 use super::utils::{copy_to_clipboard, get_error_message};
-use super::result::{create_result_window, update_window_text, WindowType, link_windows, RefineContext};
+use super::blocks::show_block_overlay;
+use super::result::{create_result_window, update_window_text, WindowType, link_windows, RefineContext, set_window_history_id, set_window_speakers, set_window_cached, load_conversation};
 
 // --- PROCESSING WINDOW STATIC STATE ---
 static REGISTER_PROC_CLASS: Once = Once::new();
@@ -25,12 +34,22 @@ lazy_static::lazy_static! {
     static ref PROC_STATES: Mutex<HashMap<isize, ProcessingState>> = Mutex::new(HashMap::new());
 }
 
+// Title bar note on a result that came from the result cache
+pub fn cached_tag(ui_language: &str) -> &'static str {
+    match ui_language {
+        "vi" => "(đã lưu)",
+        "ko" => "(캐시)",
+        _ => "(cached)",
+    }
+}
+
 // --- MAIN ENTRY POINT FOR PROCESSING ---
 pub fn start_processing_pipeline(
     cropped_img: ImageBuffer<Rgba<u8>, Vec<u8>>, 
     screen_rect: RECT, 
     config: Config, 
-    preset: Preset
+    preset: Preset,
+    history_parent: Option<i64>
 ) {
     let hide_overlay = preset.hide_overlay;
 
@@ -43,6 +62,10 @@ pub fn start_processing_pipeline(
     // Prepare Refine Context (Image)
     let mut png_data = Vec::new();
     let _ = cropped_img.write_to(&mut std::io::Cursor::new(&mut png_data), image::ImageFormat::Png);
+    let prep_options = PrepOptions::from_preset(&preset);
+    let ocr_language = preset.ocr_language.clone();
+    let local_ocr_fallback = preset.local_ocr_fallback;
+    let original_png = png_data.clone();
     let refine_context = RefineContext::Image(png_data);
 
     // NEW LOGIC: Dynamic Prompt Mode
@@ -90,14 +113,18 @@ pub fn start_processing_pipeline(
     let ui_language = config.ui_language.clone();
     
     // Prepare Prompt
-    let mut final_prompt = preset.prompt.clone();
-    for (key, value) in &preset.language_vars {
-        final_prompt = final_prompt.replace(&format!("{{{}}}", key), value);
+    let mut final_prompt = preset.expanded_prompt();
+
+    // In-place mode needs the complete JSON reply before anything can be drawn.
+    // Block positions would not survive the tiling of stitched scroll captures.
+    let overlay_blocks = preset.overlay_blocks && !preset.scroll_capture;
+    if overlay_blocks {
+        final_prompt.push_str(OCR_BLOCKS_INSTRUCTION);
     }
-    final_prompt = final_prompt.replace("{language}", &preset.selected_language);
     
-    let streaming_enabled = preset.streaming_enabled;
-    let use_json_format = preset.id == "preset_translate";
+    // Structured output is validated as a whole, so it is never streamed
+    let output_schema = if overlay_blocks { None } else { OutputSchema::from_preset(&preset) };
+    let streaming_enabled = preset.streaming_enabled && !overlay_blocks && output_schema.is_none();
     let auto_copy = preset.auto_copy;
     let auto_paste_newline = preset.auto_paste_newline;
     let do_retranslate = preset.retranslate;
@@ -106,6 +133,15 @@ pub fn start_processing_pipeline(
     let retranslate_streaming_enabled = preset.retranslate_streaming_enabled;
     let retranslate_auto_copy = preset.retranslate_auto_copy;
     let cropped_for_history = cropped_img.clone();
+    // Tall stitched pages are sent in tiles the model can read at full resolution
+    let tiles = if preset.scroll_capture {
+        split_tiles(&cropped_img, (cropped_img.width() * 3 / 2).clamp(768, 2048))
+    } else {
+        Vec::new()
+    };
+    let cache_results = preset.cache_results && config.result_cache_max_entries > 0;
+    let cache_ttl_hours = config.result_cache_ttl_hours;
+    let cache_max_entries = config.result_cache_max_entries;
 
     // NEW: Capture the target window we stored in main.rs
     let target_window_for_paste = if let Ok(app) = crate::APP.lock() {
@@ -122,67 +158,149 @@ pub fn start_processing_pipeline(
         
         let (tx_hwnd, rx_hwnd) = std::sync::mpsc::channel();
 
-        let api_res = translate_image_streaming(
-            &groq_api_key, 
-            &gemini_api_key, 
-            final_prompt, 
-            model_name, 
-            provider.clone(), 
-            cropped_img, 
-            streaming_enabled, 
-            use_json_format,
-            |chunk| {
-                let mut text = acc_vis_clone.lock().unwrap();
-                text.push_str(chunk);
+        // Tiled captures are prepared tile by tile, the stitched page is never uploaded
+        let prepared = tiles.is_empty().then(|| prepare_image(&cropped_img, &original_png, &prep_options));
+        let mut on_chunk = |chunk: &str| {
+            let mut text = acc_vis_clone.lock().unwrap();
+            text.push_str(chunk);
+            
+            if !first_chunk_received && !overlay_blocks {
+                first_chunk_received = true;
+                
+                // Signal Processing Overlay to Fade Out
+                if processing_hwnd.0 != 0 {
+                    unsafe { PostMessageW(processing_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
+                }
+
+                // Spawn the Result Window Thread
+                let rect_copy = screen_rect;
+                let refine_ctx_copy = refine_context.clone();
+                let mid_copy = model_id.clone();
+                let prov_copy = provider.clone();
+                let stream_copy = streaming_enabled;
+                let hide_copy = hide_overlay;
+                let tx_hwnd_clone = tx_hwnd.clone();
                 
-                if !first_chunk_received {
-                    first_chunk_received = true;
+                std::thread::spawn(move || {
+                    let hwnd = create_result_window(
+                        rect_copy,
+                        WindowType::Primary,
+                        refine_ctx_copy,
+                        mid_copy,
+                        prov_copy,
+                        stream_copy,
+                        false
+                    );
                     
-                    // Signal Processing Overlay to Fade Out
-                    if processing_hwnd.0 != 0 {
-                        unsafe { PostMessageW(processing_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
+                    // Only show the text result if NOT hidden
+                    if !hide_copy {
+                        unsafe { ShowWindow(hwnd, SW_SHOW); }
                     }
-
-                    // Spawn the Result Window Thread
-                    let rect_copy = screen_rect;
-                    let refine_ctx_copy = refine_context.clone();
-                    let mid_copy = model_id.clone();
-                    let prov_copy = provider.clone();
-                    let stream_copy = streaming_enabled;
-                    let hide_copy = hide_overlay;
-                    let tx_hwnd_clone = tx_hwnd.clone();
+                    let _ = tx_hwnd_clone.send(hwnd);
                     
-                    std::thread::spawn(move || {
-                        let hwnd = create_result_window(
-                            rect_copy,
-                            WindowType::Primary,
-                            refine_ctx_copy,
-                            mid_copy,
-                            prov_copy,
-                            stream_copy,
-                            false
-                        );
-                        
-                        // Only show the text result if NOT hidden
-                        if !hide_copy {
-                            unsafe { ShowWindow(hwnd, SW_SHOW); }
-                        }
-                        let _ = tx_hwnd_clone.send(hwnd);
-                        
-                        unsafe {
-                            let mut msg = MSG::default();
-                            while GetMessageW(&mut msg, None, 0, 0).into() {
-                                TranslateMessage(&msg);
-                                DispatchMessageW(&msg);
-                                if !IsWindow(hwnd).as_bool() { break; }
-                            }
+                    unsafe {
+                        let mut msg = MSG::default();
+                        while GetMessageW(&mut msg, None, 0, 0).into() {
+                            TranslateMessage(&msg);
+                            DispatchMessageW(&msg);
+                            if !IsWindow(hwnd).as_bool() { break; }
                         }
-                    });
+                    }
+                });
+            }
+        };
+
+        // Same pixels + prompt + model (+ options that change the reply) = same answer
+        let cache_key = cache_results.then(|| {
+            let variant = format!("{}x{}|{:?}|{:?}|{}", cropped_img.width(), cropped_img.height(), prep_options, output_schema, ocr_language);
+            crate::result_cache::request_key(cropped_img.as_raw(), &final_prompt, &model_name, &variant)
+        });
+        // Re-running a history entry asks for a fresh answer
+        let cached_text = cache_key.as_deref()
+            .filter(|_| history_parent.is_none())
+            .and_then(|key| crate::result_cache::lookup(key, cache_ttl_hours));
+        let from_cache = cached_text.is_some();
+
+        // Local models run offline and need no key
+        let used_local_fallback = std::cell::Cell::new(false);
+        let send = |image: &PreparedImage, on_chunk: &mut dyn FnMut(&str)| {
+            if provider == "local" {
+                return recognize_image_streaming(&model_name, image, &ocr_language, on_chunk);
+            }
+            let res = translate_image_streaming(
+                &groq_api_key, 
+                &gemini_api_key, 
+                final_prompt.clone(), 
+                model_name.clone(), 
+                provider.clone(), 
+                image, 
+                streaming_enabled, 
+                output_schema.as_ref(),
+                &mut *on_chunk
+            );
+            // No key or no connection: extract the text offline, retranslate (if enabled) translates it
+            match res {
+                Err(e) if local_ocr_fallback && matches!(e.to_string().as_str(), "NO_API_KEY" | "NETWORK_ERROR") => {
+                    used_local_fallback.set(true);
+                    recognize_with_backend(OcrBackend::Windows, image, &ocr_language, on_chunk)
                 }
+                res => res,
             }
-        );
+        };
 
-        let result_hwnd = if first_chunk_received {
+        let api_res = if let Some(text) = cached_text {
+            on_chunk(&text);
+            Ok(text)
+        } else if let Some(prepared) = &prepared {
+            send(prepared, &mut on_chunk)
+        } else {
+            // One request per tile, answers joined in page order
+            let mut parts = Vec::new();
+            let mut failure = None;
+            for (i, tile) in tiles.iter().enumerate() {
+                let mut tile_png = Vec::new();
+                let _ = tile.write_to(&mut std::io::Cursor::new(&mut tile_png), image::ImageFormat::Png);
+                let prepared_tile = prepare_image(tile, &tile_png, &prep_options);
+                if i > 0 {
+                    on_chunk("\n\n");
+                }
+                match send(&prepared_tile, &mut on_chunk) {
+                    Ok(text) => parts.push(text),
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
+                    }
+                }
+            }
+            match failure {
+                Some(e) => Err(e),
+                None => Ok(parts.join("\n\n")),
+            }
+        };
+
+        // An offline fallback is not the answer the key stands for
+        if let (Some(key), Ok(text), false, false) = (cache_key, &api_res, from_cache, used_local_fallback.get()) {
+            crate::result_cache::store(key, text.clone(), cache_ttl_hours, cache_max_entries);
+        }
+
+        // Parsed blocks replace the single window; an unparseable reply falls back to plain text
+        let ocr_blocks = match &api_res {
+            Ok(raw) if overlay_blocks => parse_ocr_blocks(raw),
+            _ => None,
+        };
+        let api_res = match (&ocr_blocks, api_res) {
+            (Some(blocks), Ok(_)) => Ok(blocks_text(blocks)),
+            (_, res) => res,
+        };
+        let showing_blocks = ocr_blocks.is_some();
+
+        let result_hwnd = if let Some(blocks) = &ocr_blocks {
+            if processing_hwnd.0 != 0 {
+                unsafe { PostMessageW(processing_hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)); }
+            }
+            // Hidden overlay: nothing is opened, history and auto copy below still run
+            Some(if hide_overlay { HWND(0) } else { show_block_overlay(blocks, screen_rect) })
+        } else if first_chunk_received {
             rx_hwnd.recv().ok()
         } else {
              if processing_hwnd.0 != 0 {
@@ -218,10 +336,26 @@ pub fn start_processing_pipeline(
         if let Some(r_hwnd) = result_hwnd {
             match api_res {
                 Ok(full_text) => {
-                    if !hide_overlay { update_window_text(r_hwnd, &full_text); }
+                    if !hide_overlay && !showing_blocks { update_window_text(r_hwnd, &full_text); }
+                    if from_cache && !showing_blocks {
+                        set_window_cached(r_hwnd, cached_tag(&ui_language));
+                    }
                     
                     if let Ok(app_lock) = crate::APP.lock() {
-                        app_lock.history.save_image(cropped_for_history, full_text.clone());
+                        let history_id = match history_parent {
+                            Some(parent_id) => app_lock.history.save_rerun(parent_id, full_text.clone(), model_id.clone(), Vec::new()),
+                            None => {
+                                // Keep what was actually uploaded next to the capture when it differs
+                                // (tiled uploads have no single processed image)
+                                let processed = prepared.as_ref()
+                                    .filter(|_| !prep_options.is_passthrough())
+                                    .map(|prepared| (prepared.data.clone(), prepared.encoding.extension()));
+                                app_lock.history.save_image(cropped_for_history, full_text.clone(), processed)
+                            },
+                        };
+                        if !showing_blocks {
+                            set_window_history_id(r_hwnd, history_id);
+                        }
                     }
 
                     // UPDATED: Logic for Auto Copy AND Auto Paste
@@ -252,7 +386,8 @@ pub fn start_processing_pipeline(
                          });
                     }
 
-                    if do_retranslate && !full_text.trim().is_empty() {
+                    // In-place blocks are already translated where they belong
+                    if do_retranslate && !showing_blocks && !full_text.trim().is_empty() {
                          let text_to_retrans = full_text.clone();
                          let g_key = groq_api_key.clone();
                          let gm_key = gemini_api_key.clone();
@@ -506,153 +641,332 @@ unsafe extern "system" fn processing_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
     }
 }
 
-pub fn show_audio_result(preset: crate::config::Preset, text: String, rect: RECT, retrans_rect: Option<RECT>) {
+// Creates a result window on its own message-loop thread and returns its handle
+pub fn spawn_result_window(rect: RECT, win_type: WindowType, context: RefineContext, model_id: String, provider: String, streaming: bool, show: bool) -> HWND {
+     let (tx_hwnd, rx_hwnd) = std::sync::mpsc::channel();
+     std::thread::spawn(move || {
+         let hwnd = create_result_window(rect, win_type, context, model_id, provider, streaming, false);
+         if show {
+             unsafe { ShowWindow(hwnd, SW_SHOW); }
+         }
+         let _ = tx_hwnd.send(hwnd);
+
+         unsafe {
+             let mut msg = MSG::default();
+             while GetMessageW(&mut msg, None, 0, 0).into() {
+                 TranslateMessage(&msg);
+                 DispatchMessageW(&msg);
+                 if !IsWindow(hwnd).as_bool() { break; }
+             }
+         }
+     });
+     rx_hwnd.recv().unwrap_or(HWND(0))
+}
+
+// Creates the primary audio result window.
+// Long recordings and live mode open it early so partial transcripts can stream in.
+pub fn open_audio_result_window(preset: &crate::config::Preset, context: RefineContext, rect: RECT) -> HWND {
+     let model_id = preset.model.clone();
+     let model_config = crate::model_config::get_model_by_id(&model_id);
+     let provider = model_config.map(|m| m.provider).unwrap_or("groq".to_string());
+
+     spawn_result_window(rect, WindowType::Primary, context, model_id, provider, preset.streaming_enabled, !preset.hide_overlay)
+}
+
+// Translation window for live mode, filled segment by segment
+pub fn open_live_translation_window(preset: &crate::config::Preset, rect: RECT, primary_hwnd: HWND) -> HWND {
+     let (model_id, provider) = match crate::model_config::get_model_by_id(&preset.retranslate_model) {
+         Some(m) => (m.id, m.provider),
+         None => ("fast_text".to_string(), "groq".to_string()),
+     };
+
+     let hwnd = spawn_result_window(rect, WindowType::SecondaryExplicit, RefineContext::None, model_id, provider, preset.retranslate_streaming_enabled, !preset.hide_overlay);
+     link_windows(primary_hwnd, hwnd);
+     hwnd
+}
+
+// Puts the final transcript into the primary window, then handles auto copy/paste,
+// subtitle export and retranslation. The [mm:ss] markers are for the window only,
+// copy/paste and retranslation get the plain text.
+pub fn finish_audio_result(preset: crate::config::Preset, transcript: Transcript, primary_hwnd: HWND, retrans_rect: Option<RECT>, history_id: Option<i64>) {
+     let display_text = transcript.display_text();
+     let text = transcript.text.clone();
      let hide_overlay = preset.hide_overlay;
      let auto_copy = preset.auto_copy;
      let auto_paste_newline = preset.auto_paste_newline;
      let retranslate = preset.retranslate && retrans_rect.is_some();
      let retranslate_to = preset.retranslate_to.clone();
+     let retranslate_lang = retranslate_to.clone();
      let retranslate_model_id = preset.retranslate_model.clone();
      let retranslate_streaming_enabled = preset.retranslate_streaming_enabled;
      let retranslate_auto_copy = preset.retranslate_auto_copy;
-     
-     let model_id = preset.model.clone();
-     let model_config = crate::model_config::get_model_by_id(&model_id);
-     let provider = model_config.map(|m| m.provider).unwrap_or("groq".to_string());
-     let streaming = preset.streaming_enabled;
-     
-     std::thread::spawn(move || {
-         let primary_hwnd = create_result_window(
-             rect,
-             WindowType::Primary,
-             RefineContext::None,
-             model_id,
-             provider,
-             streaming,
-             false
-         );
-        if !hide_overlay {
-             unsafe { ShowWindow(primary_hwnd, SW_SHOW); }
-             update_window_text(primary_hwnd, &text);
+
+     if let Some(id) = history_id {
+         set_window_history_id(primary_hwnd, id);
+     }
+     let speakers = transcript.speakers();
+     if !speakers.is_empty() {
+         set_window_speakers(primary_hwnd, speakers);
+     }
+     if !hide_overlay {
+         update_window_text(primary_hwnd, &display_text);
+     }
+
+     // --- AUTO PASTE LOGIC FOR AUDIO ---
+     if auto_copy && !text.trim().is_empty() {
+         let target_window = if let Ok(app) = crate::APP.lock() {
+            app.last_active_window
+         } else { None };
+         
+         let mut txt_for_copy = text.clone();
+         
+         // MODIFY CONTENT: Append Newline if enabled
+         if auto_paste_newline {
+             txt_for_copy.push_str("\r\n");
          }
          
-         // --- AUTO PASTE LOGIC FOR AUDIO ---
-         if auto_copy && !text.trim().is_empty() {
-             let target_window = if let Ok(app) = crate::APP.lock() {
-                app.last_active_window
-             } else { None };
-             
-             let mut txt_for_copy = text.clone();
-             
-             // MODIFY CONTENT: Append Newline if enabled
-             if auto_paste_newline {
-                 txt_for_copy.push_str("\r\n");
-             }
+         // Logic: Only paste if Hide Overlay is ON and we have a target window
+         let should_paste = hide_overlay && target_window.is_some();
+
+         std::thread::spawn(move || {
+             std::thread::sleep(std::time::Duration::from_millis(200));
+             copy_to_clipboard(&txt_for_copy, HWND(0));
              
-             // Logic: Only paste if Hide Overlay is ON and we have a target window
-             let should_paste = hide_overlay && target_window.is_some();
-
-             std::thread::spawn(move || {
-                 std::thread::sleep(std::time::Duration::from_millis(200));
-                 copy_to_clipboard(&txt_for_copy, HWND(0));
-                 
-                 if should_paste {
-                     if let Some(hwnd) = target_window {
-                         crate::overlay::utils::force_focus_and_paste(hwnd);
-                     }
+             if should_paste {
+                 if let Some(hwnd) = target_window {
+                     crate::overlay::utils::force_focus_and_paste(hwnd);
                  }
-             });
+             }
+         });
+     }
+     // ----------------------------------
+
+     // --- SUBTITLE EXPORT ---
+     let subtitle_format = SubtitleFormat::from_preset(&preset.subtitle_format)
+         .filter(|_| !transcript.segments.is_empty());
+     let subtitle_stem = format!("subtitles_{}", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+     if let Some(format) = subtitle_format {
+         if let Err(e) = crate::subtitle::write_subtitle_file(&transcript.segments, format, &subtitle_stem, None) {
+             eprintln!("Subtitle export error: {}", e);
          }
-         // ----------------------------------
-
-        if retranslate && !text.trim().is_empty() {
-            let rect_sec = retrans_rect.unwrap();
-            let text_for_retrans = text.clone();
-            let (groq_key, gemini_key) = {
-                let app = crate::APP.lock().unwrap();
-                (app.config.api_key.clone(), app.config.gemini_api_key.clone())
+     }
+     let segments = transcript.segments;
+
+    if retranslate && !text.trim().is_empty() {
+        let rect_sec = retrans_rect.unwrap();
+        let text_for_retrans = text.clone();
+        let (groq_key, gemini_key) = {
+            let app = crate::APP.lock().unwrap();
+            (app.config.api_key.clone(), app.config.gemini_api_key.clone())
+        };
+        
+        std::thread::spawn(move || {
+            let tm_config = crate::model_config::get_model_by_id(&retranslate_model_id);
+            let (tm_id, tm_name, tm_provider) = match tm_config {
+            Some(m) => (m.id, m.full_name, m.provider),
+            None => ("fast_text".to_string(), "openai/gpt-oss-20b".to_string(), "groq".to_string())
             };
             
-            std::thread::spawn(move || {
-                let tm_config = crate::model_config::get_model_by_id(&retranslate_model_id);
-                let (tm_id, tm_name, tm_provider) = match tm_config {
-                Some(m) => (m.id, m.full_name, m.provider),
-                None => ("fast_text".to_string(), "openai/gpt-oss-20b".to_string(), "groq".to_string())
-                };
-                
-                let secondary_hwnd = create_result_window(
-                rect_sec,
-                WindowType::SecondaryExplicit,
-                RefineContext::None,
-                tm_id,
-                tm_provider.clone(),
-                retranslate_streaming_enabled,
-                false
-                );
-                link_windows(primary_hwnd, secondary_hwnd);
-                
-                if !hide_overlay {
-                unsafe { ShowWindow(secondary_hwnd, SW_SHOW); }
-                update_window_text(secondary_hwnd, "");
-                }
+            let secondary_hwnd = create_result_window(
+            rect_sec,
+            WindowType::SecondaryExplicit,
+            RefineContext::None,
+            tm_id,
+            tm_provider.clone(),
+            retranslate_streaming_enabled,
+            false
+            );
+            link_windows(primary_hwnd, secondary_hwnd);
+            
+            if !hide_overlay {
+            unsafe { ShowWindow(secondary_hwnd, SW_SHOW); }
+            update_window_text(secondary_hwnd, "");
+            }
 
-                std::thread::spawn(move || {
-                let acc_text = Arc::new(Mutex::new(String::new()));
-                let acc_text_clone = acc_text.clone();
-
-                        let text_res = translate_text_streaming(
-                            &groq_key,
-                            &gemini_key,
-                            text_for_retrans,
-                            retranslate_to,
-                            tm_name,
-                            tm_provider,
-                            retranslate_streaming_enabled,
-                            false,
-                            |chunk| {
-                                let mut t = acc_text_clone.lock().unwrap();
-                                t.push_str(chunk);
-                                if !hide_overlay {
-                                    update_window_text(secondary_hwnd, &t);
-                                }
-                            }
-                        );
-                        
-                        if let Ok(final_text) = text_res {
+            std::thread::spawn(move || {
+            let acc_text = Arc::new(Mutex::new(String::new()));
+            let acc_text_clone = acc_text.clone();
+
+                    let text_res = translate_text_streaming(
+                        &groq_key,
+                        &gemini_key,
+                        text_for_retrans,
+                        retranslate_to,
+                        tm_name,
+                        tm_provider,
+                        retranslate_streaming_enabled,
+                        false,
+                        |chunk| {
+                            let mut t = acc_text_clone.lock().unwrap();
+                            t.push_str(chunk);
                             if !hide_overlay {
-                                update_window_text(secondary_hwnd, &final_text);
+                                update_window_text(secondary_hwnd, &t);
                             }
-                            if retranslate_auto_copy {
-                                std::thread::spawn(move || {
-                                    std::thread::sleep(std::time::Duration::from_millis(100));
-                                    copy_to_clipboard(&final_text, HWND(0));
-                                });
+                        }
+                    );
+                    
+                    if let Ok(final_text) = text_res {
+                        if !hide_overlay {
+                            update_window_text(secondary_hwnd, &final_text);
+                        }
+                        if let Some(id) = history_id {
+                            if let Ok(app) = crate::APP.lock() {
+                                app.history.set_translation(id, final_text.clone(), retranslate_lang.clone());
                             }
-                        } else if let Err(e) = text_res {
-                            if !hide_overlay {
-                                update_window_text(secondary_hwnd, &format!("Error: {}", e));
+                        }
+                        if let Some(format) = subtitle_format {
+                            let aligned = crate::subtitle::align_translation(&segments, &final_text);
+                            if let Err(e) = crate::subtitle::write_subtitle_file(&aligned, format, &subtitle_stem, Some(&retranslate_lang)) {
+                                eprintln!("Subtitle export error: {}", e);
                             }
                         }
-                    });
-
-                    unsafe {
-                        let mut msg = MSG::default();
-                        while GetMessageW(&mut msg, None, 0, 0).into() {
-                            TranslateMessage(&msg);
-                            DispatchMessageW(&msg);
-                            if !IsWindow(secondary_hwnd).as_bool() { break; }
+                        if retranslate_auto_copy {
+                            std::thread::spawn(move || {
+                                std::thread::sleep(std::time::Duration::from_millis(100));
+                                copy_to_clipboard(&final_text, HWND(0));
+                            });
+                        }
+                    } else if let Err(e) = text_res {
+                        if !hide_overlay {
+                            update_window_text(secondary_hwnd, &format!("Error: {}", e));
                         }
                     }
                 });
+
+                unsafe {
+                    let mut msg = MSG::default();
+                    while GetMessageW(&mut msg, None, 0, 0).into() {
+                        TranslateMessage(&msg);
+                        DispatchMessageW(&msg);
+                        if !IsWindow(secondary_hwnd).as_bool() { break; }
+                    }
+                }
+            });
+    }
+}
+
+// --- HISTORY RE-RUN ---
+// Feeds the stored media of a history entry through another preset/model.
+// The new result is saved as a child of the original entry.
+pub fn rerun_history_item(item: HistoryItem, preset: Preset) {
+    let parent_id = item.parent_id.unwrap_or(item.id);
+    let path = crate::history::get_media_path(&item.media_path);
+
+    match item.item_type {
+        HistoryType::Image => {
+            let img = match image::open(&path) {
+                Ok(img) => img.to_rgba8(),
+                Err(e) => {
+                    eprintln!("Re-run error: failed to load {}: {}", path.display(), e);
+                    show_rerun_error(&preset, "HISTORY_MEDIA_MISSING");
+                    return;
+                }
+            };
+            let config = crate::APP.lock().unwrap().config.clone();
+
+            let (screen_w, screen_h) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+            let w = (img.width() as i32).clamp(300, screen_w * 4 / 5);
+            let h = (img.height() as i32).clamp(150, screen_h * 4 / 5);
+            let x = (screen_w - w) / 2;
+            let y = (screen_h - h) / 2;
+            let rect = RECT { left: x, top: y, right: x + w, bottom: y + h };
+
+            start_processing_pipeline(img, rect, config, preset, Some(parent_id));
+        },
+        HistoryType::Audio => {
+            let (speech_samples, audio_data) = match crate::api::audio::load_speech_file(&preset, &path) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    eprintln!("Re-run error: failed to load {}: {}", path.display(), e);
+                    show_rerun_error(&preset, e.code());
+                    return;
+                }
+            };
+
+            // Long recordings are split like a fresh one, a single upload would hit the size limit
+            crate::api::audio::rerun_speech(preset, &speech_samples, audio_data, parent_id);
         }
-        
-        unsafe {
-            let mut msg = MSG::default();
-            while GetMessageW(&mut msg, None, 0, 0).into() {
-                TranslateMessage(&msg);
-                DispatchMessageW(&msg);
-                if !IsWindow(primary_hwnd).as_bool() { break; }
+    }
+}
+
+// A failed re-run has no capture to anchor to, so the error gets a centered window
+pub fn show_rerun_error(preset: &Preset, error: &str) {
+    let ui_language = crate::APP.lock().map(|app| app.config.ui_language.clone()).unwrap_or_default();
+    let provider = crate::model_config::get_model_by_id(&preset.model)
+        .map(|m| m.provider)
+        .unwrap_or_else(|| "groq".to_string());
+
+    let (screen_w, screen_h) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    let (w, h) = (400, 120);
+    let x = (screen_w - w) / 2;
+    let y = (screen_h - h) / 2;
+    let rect = RECT { left: x, top: y, right: x + w, bottom: y + h };
+
+    let hwnd = spawn_result_window(rect, WindowType::Primary, RefineContext::None, preset.model.clone(), provider, false, true);
+    update_window_text(hwnd, &get_error_message(error, &ui_language));
+}
+
+// --- HISTORY CONVERSATION ---
+// Reopens a history entry in a result window with its saved refine thread,
+// ready for the next instruction. The thread keeps being saved to the same entry.
+pub fn reopen_history_conversation(item: HistoryItem) {
+    let path = crate::history::get_media_path(&item.media_path);
+
+    let (context, model_type) = match item.item_type {
+        HistoryType::Image => match std::fs::read(&path) {
+            Ok(png_data) => (RefineContext::Image(png_data), crate::model_config::ModelType::Vision),
+            Err(e) => {
+                eprintln!("Reopen error: failed to load {}: {}", path.display(), e);
+                (RefineContext::None, crate::model_config::ModelType::Text)
             }
+        },
+        HistoryType::Audio => match std::fs::read(&path) {
+            Ok(audio_data) => (RefineContext::Audio(audio_data), crate::model_config::ModelType::Audio),
+            Err(e) => {
+                eprintln!("Reopen error: failed to load {}: {}", path.display(), e);
+                (RefineContext::None, crate::model_config::ModelType::Text)
+            }
+        },
+    };
+
+    // Original entries don't record their model, fall back to the first enabled one of the right kind
+    let model = crate::model_config::get_model_by_id(&item.model_id)
+        .filter(|m| m.model_type == model_type)
+        .or_else(|| crate::model_config::get_all_models().iter()
+            .find(|m| m.enabled && m.model_type == model_type)
+            .cloned());
+    let (model_id, provider) = match model {
+        Some(m) => (m.id, m.provider),
+        None => ("scout".to_string(), "groq".to_string()),
+    };
+
+    let mut conversation = item.conversation.clone();
+    if conversation.is_empty() {
+        conversation.push(crate::api::ChatTurn::assistant(item.text.clone()));
+    }
+    let current_text = conversation.iter().rev()
+        .find(|t| t.role == "assistant")
+        .map(|t| t.content.clone())
+        .unwrap_or_else(|| item.text.clone());
+
+    let (screen_w, screen_h) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    let w = 600.min(screen_w * 4 / 5);
+    let h = 300.min(screen_h * 4 / 5);
+    let x = (screen_w - w) / 2;
+    let y = (screen_h - h) / 2;
+    let rect = RECT { left: x, top: y, right: x + w, bottom: y + h };
+
+    let hwnd = create_result_window(rect, WindowType::Primary, context, model_id, provider, false, true);
+    load_conversation(hwnd, conversation, item.id);
+    unsafe { ShowWindow(hwnd, SW_SHOW); }
+    update_window_text(hwnd, &current_text);
+
+    unsafe {
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).into() {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+            if !IsWindow(hwnd).as_bool() { break; }
         }
-    });
+    }
 }
@@ -5,6 +5,7 @@ use windows::Win32::System::LibraryLoader::*;
 use windows::core::*;
 use std::sync::{Arc, atomic::{AtomicBool, Ordering, AtomicU32}, Once, Mutex};
 use crate::APP;
+use crate::audio::vad::VadState;
 
 static mut RECORDING_HWND: HWND = HWND(0);
 static mut IS_RECORDING: bool = false;
@@ -20,6 +21,15 @@ lazy_static::lazy_static! {
 static mut VIS_HEAD: usize = 0;
 static CURRENT_RMS: AtomicU32 = AtomicU32::new(0);
 
+// Voice activity (only tracked when the preset auto-stops on silence)
+static VAD_STATE: AtomicU32 = AtomicU32::new(0); // 0 = waiting, 1 = speech, 2 = silence
+static VAD_SILENCE_MS: AtomicU32 = AtomicU32::new(0);
+
+// Localized audio error shown instead of the recording status
+lazy_static::lazy_static! {
+    static ref RECORDING_ERROR: Mutex<Option<String>> = Mutex::new(None);
+}
+
 // --- UI CONSTANTS ---
 const UI_WIDTH: i32 = 350;   // More compact width
 const UI_HEIGHT: i32 = 80;   // Reduced height
@@ -31,6 +41,25 @@ pub fn update_audio_viz(rms: f32) {
     CURRENT_RMS.store(bits, Ordering::Relaxed);
 }
 
+pub fn update_vad_state(state: VadState, silence_ms: u32) {
+    let code = match state {
+        VadState::Waiting => 0,
+        VadState::Speech => 1,
+        VadState::Silence => 2,
+    };
+    VAD_STATE.store(code, Ordering::Relaxed);
+    VAD_SILENCE_MS.store(silence_ms, Ordering::Relaxed);
+}
+
+pub fn show_recording_error(message: String) {
+    *RECORDING_ERROR.lock().unwrap() = Some(message);
+    unsafe {
+        if RECORDING_HWND.0 != 0 {
+            PostMessageW(RECORDING_HWND, WM_TIMER, WPARAM(0), LPARAM(0));
+        }
+    }
+}
+
 // Shared flag for the audio thread
 lazy_static::lazy_static! {
     pub static ref AUDIO_STOP_SIGNAL: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
@@ -70,6 +99,9 @@ pub fn show_recording_overlay(preset_idx: usize) {
         AUDIO_STOP_SIGNAL.store(false, Ordering::SeqCst);
          AUDIO_PAUSE_SIGNAL.store(false, Ordering::SeqCst);
          AUDIO_ABORT_SIGNAL.store(false, Ordering::SeqCst); // Reset abort signal
+         VAD_STATE.store(0, Ordering::Relaxed);
+         VAD_SILENCE_MS.store(0, Ordering::Relaxed);
+         *RECORDING_ERROR.lock().unwrap() = None;
           
           // Reset viz
           VIS_HEAD = 0;
@@ -160,8 +192,9 @@ unsafe fn paint_layered_window(hwnd: HWND, width: i32, height: i32, alpha: u8) {
     let mem_dc = CreateCompatibleDC(screen_dc);
     let old_bitmap = SelectObject(mem_dc, bitmap);
 
+    let error = RECORDING_ERROR.lock().unwrap().clone();
     let is_waiting = AUDIO_STOP_SIGNAL.load(Ordering::SeqCst);
-    let should_animate = !IS_PAUSED || is_waiting;
+    let should_animate = (!IS_PAUSED || is_waiting) && error.is_none();
     
     if !p_bits.is_null() {
         let pixels = std::slice::from_raw_parts_mut(p_bits as *mut u32, (width * height) as usize);
@@ -237,7 +270,7 @@ unsafe fn paint_layered_window(hwnd: HWND, width: i32, height: i32, alpha: u8) {
         }
 
         // 2. Draw Viz Bars (if valid)
-         if !is_waiting && !IS_PAUSED {
+         if !is_waiting && !IS_PAUSED && error.is_none() {
              let viz_w = 220.0;
              let bar_w = 3.0;
              let spacing = 2.0;
@@ -337,13 +370,16 @@ unsafe fn paint_layered_window(hwnd: HWND, width: i32, height: i32, alpha: u8) {
     let hfont_main = CreateFontW(19, 0, 0, 0, FW_BOLD.0 as i32, 0, 0, 0, DEFAULT_CHARSET.0 as u32, OUT_DEFAULT_PRECIS.0 as u32, CLIP_DEFAULT_PRECIS.0 as u32, CLEARTYPE_QUALITY.0 as u32, (VARIABLE_PITCH.0 | FF_SWISS.0) as u32, w!("Segoe UI"));
     let old_font = SelectObject(mem_dc, hfont_main);
 
-    let src_text = if is_waiting {
+    let src_text = if error.is_some() {
+        "Lỗi ghi âm"
+    } else if is_waiting {
         "Đang xử lý..."
     } else {
         if CURRENT_PRESET_IDX < APP.lock().unwrap().config.presets.len() {
              let p = &APP.lock().unwrap().config.presets[CURRENT_PRESET_IDX];
              if IS_PAUSED { "Tạm dừng" } 
              else if p.audio_source == "device" { "Ghi âm máy..." } 
+             else if p.audio_source == "mix" { "Ghi âm mic + máy..." }
              else { "Ghi âm mic..." }
         } else { "Recording..." }
     };
@@ -355,14 +391,33 @@ unsafe fn paint_layered_window(hwnd: HWND, width: i32, height: i32, alpha: u8) {
     SelectObject(mem_dc, old_font);
     DeleteObject(hfont_main);
 
-    // Only show sub-text if not processing
-    if !is_waiting {
+    // Only show sub-text if not processing (errors are always shown)
+    if !is_waiting || error.is_some() {
         let hfont_sub = CreateFontW(14, 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, DEFAULT_CHARSET.0 as u32, OUT_DEFAULT_PRECIS.0 as u32, CLIP_DEFAULT_PRECIS.0 as u32, CLEARTYPE_QUALITY.0 as u32, (VARIABLE_PITCH.0 | FF_SWISS.0) as u32, w!("Segoe UI"));
         SelectObject(mem_dc, hfont_sub);
         SetTextColor(mem_dc, COLORREF(0x00DDDDDD)); 
 
-        let sub_text = "Bấm hotkey lần nữa để xử lý âm thanh";
-        let mut sub_text_w = crate::overlay::utils::to_wstring(sub_text);
+        let auto_stop_secs = APP.lock().unwrap().config.presets
+            .get(CURRENT_PRESET_IDX)
+            .map(|p| p.auto_stop_silence_secs)
+            .unwrap_or(0.0);
+
+        // With auto-stop the hint follows the voice activity instead
+        let sub_text = if let Some(message) = &error {
+            message.clone()
+        } else if auto_stop_secs <= 0.0 || IS_PAUSED {
+            "Bấm hotkey lần nữa để xử lý âm thanh".to_string()
+        } else {
+            match VAD_STATE.load(Ordering::Relaxed) {
+                0 => "Đang chờ giọng nói...".to_string(),
+                1 => "Đang nghe... (tự dừng khi im lặng)".to_string(),
+                _ => {
+                    let silence = VAD_SILENCE_MS.load(Ordering::Relaxed) as f32 / 1000.0;
+                    format!("Im lặng - tự dừng sau {:.1}s", (auto_stop_secs - silence).max(0.0))
+                }
+            }
+        };
+        let mut sub_text_w = crate::overlay::utils::to_wstring(&sub_text);
         let mut tr_sub = RECT { left: 0, top: 47, right: width, bottom: height };
         DrawTextW(mem_dc, &mut sub_text_w, &mut tr_sub, DT_CENTER | DT_TOP | DT_SINGLELINE);
 
@@ -0,0 +1,233 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::UI::WindowsAndMessaging::*;
+use image::{GenericImageView, ImageBuffer, Rgba};
+
+use super::process::start_processing_pipeline;
+use super::scroll::capture_scrolling;
+use super::watch::start_watch;
+use crate::config::{CaptureRegion, Config, Preset};
+use crate::APP;
+
+// --- CAPTURE TARGETS ---
+// Presets not in "select" mode skip the selection overlay and crop a remembered
+// region, a window or a whole monitor straight from the fresh screenshot.
+
+struct MonitorLookup {
+    device: String,
+    rect: Option<RECT>,
+}
+
+unsafe extern "system" fn find_monitor_proc(hmonitor: HMONITOR, _hdc: HDC, _lprc: *mut RECT, dwdata: LPARAM) -> BOOL {
+    let lookup = &mut *(dwdata.0 as *mut MonitorLookup);
+    let mut mi = MONITORINFOEXW::default();
+    mi.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    if GetMonitorInfoW(hmonitor, &mut mi as *mut _ as *mut _).as_bool() {
+        let name = String::from_utf16_lossy(&mi.szDevice).trim_matches(char::from(0)).to_string();
+        if name == lookup.device {
+            lookup.rect = Some(mi.monitorInfo.rcMonitor);
+            return BOOL(0);
+        }
+    }
+    BOOL(1)
+}
+
+// Screen rect (virtual desktop coordinates) -> region on the monitor holding most of it
+pub fn region_from_rect(rect: RECT) -> Option<CaptureRegion> {
+    unsafe {
+        let hmonitor = MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST);
+        let mut mi = MONITORINFOEXW::default();
+        mi.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if !GetMonitorInfoW(hmonitor, &mut mi as *mut _ as *mut _).as_bool() {
+            return None;
+        }
+        let origin = mi.monitorInfo.rcMonitor;
+        Some(CaptureRegion {
+            monitor: String::from_utf16_lossy(&mi.szDevice).trim_matches(char::from(0)).to_string(),
+            left: rect.left - origin.left,
+            top: rect.top - origin.top,
+            right: rect.right - origin.left,
+            bottom: rect.bottom - origin.top,
+        })
+    }
+}
+
+fn monitor_rect(device: &str) -> Option<RECT> {
+    let mut lookup = MonitorLookup { device: device.to_string(), rect: None };
+    unsafe {
+        EnumDisplayMonitors(HDC(0), None, Some(find_monitor_proc), LPARAM(&mut lookup as *mut _ as isize));
+    }
+    lookup.rect
+}
+
+// None when the monitor is gone or the region no longer fits on it
+pub fn region_to_rect(region: &CaptureRegion) -> Option<RECT> {
+    let mon = monitor_rect(&region.monitor)?;
+    let rect = RECT {
+        left: (mon.left + region.left).max(mon.left),
+        top: (mon.top + region.top).max(mon.top),
+        right: (mon.left + region.right).min(mon.right),
+        bottom: (mon.top + region.bottom).min(mon.bottom),
+    };
+    (rect.right - rect.left > 10 && rect.bottom - rect.top > 10).then_some(rect)
+}
+
+// Visible bounds of a top-level window, clipped to the virtual screen.
+// DWM bounds leave out the invisible resize borders GetWindowRect includes.
+fn window_rect(hwnd: HWND) -> Option<RECT> {
+    unsafe {
+        if hwnd.0 == 0 || IsIconic(hwnd).as_bool() {
+            return None;
+        }
+        let mut rect = RECT::default();
+        if DwmGetWindowAttribute(hwnd, DWMWA_EXTENDED_FRAME_BOUNDS, &mut rect as *mut _ as *mut _, std::mem::size_of::<RECT>() as u32).is_err() {
+            if !GetWindowRect(hwnd, &mut rect).as_bool() {
+                return None;
+            }
+        }
+        let x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+        let y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+        let clipped = RECT {
+            left: rect.left.max(x),
+            top: rect.top.max(y),
+            right: rect.right.min(x + GetSystemMetrics(SM_CXVIRTUALSCREEN)),
+            bottom: rect.bottom.min(y + GetSystemMetrics(SM_CYVIRTUALSCREEN)),
+        };
+        (clipped.right - clipped.left > 10 && clipped.bottom - clipped.top > 10).then_some(clipped)
+    }
+}
+
+// Screen rect for the preset's capture mode, None = ask for a selection
+fn target_rect(preset: &Preset) -> Option<RECT> {
+    match preset.capture_mode.as_str() {
+        "last" => preset.last_region.as_ref().and_then(region_to_rect),
+        "fixed" => preset.fixed_region.as_ref().and_then(region_to_rect),
+        "active_window" => window_rect(unsafe { GetForegroundWindow() }),
+        "cursor_window" => unsafe {
+            let mut pt = POINT::default();
+            if !GetCursorPos(&mut pt).as_bool() {
+                return None;
+            }
+            window_rect(GetAncestor(WindowFromPoint(pt), GA_ROOT))
+        },
+        mode => mode.strip_prefix("monitor:").and_then(monitor_rect),
+    }
+}
+
+// GDI copy of a screen rect (virtual desktop coordinates)
+pub fn capture_screen_rect(rect: RECT) -> anyhow::Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+    unsafe {
+        let (x, y) = (rect.left, rect.top);
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        
+        // Validate dimensions
+        if width <= 0 || height <= 0 {
+            return Err(anyhow::anyhow!("GDI Error: Invalid screen dimensions ({} x {})", width, height));
+        }
+
+        let hdc_screen = GetDC(None);
+        if hdc_screen.0 == 0 {
+            return Err(anyhow::anyhow!("GDI Error: Failed to get screen device context"));
+        }
+        
+        let hdc_mem = CreateCompatibleDC(hdc_screen);
+        if hdc_mem.0 == 0 {
+            ReleaseDC(None, hdc_screen);
+            return Err(anyhow::anyhow!("GDI Error: Failed to create compatible device context"));
+        }
+        
+        let hbitmap = CreateCompatibleBitmap(hdc_screen, width, height);
+        
+        if hbitmap.0 == 0 {
+             DeleteDC(hdc_mem);
+             ReleaseDC(None, hdc_screen);
+             return Err(anyhow::anyhow!("GDI Error: Failed to create compatible bitmap."));
+        }
+        
+        SelectObject(hdc_mem, hbitmap);
+
+        BitBlt(hdc_mem, 0, 0, width, height, hdc_screen, x, y, SRCCOPY).ok()?;
+
+        let mut bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0 as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut buffer: Vec<u8> = vec![0; (width * height * 4) as usize];
+        GetDIBits(hdc_mem, hbitmap, 0, height as u32, Some(buffer.as_mut_ptr() as *mut _), &mut bmi, DIB_RGB_COLORS);
+
+        for chunk in buffer.chunks_exact_mut(4) {
+            chunk.swap(0, 2);
+            chunk[3] = 255;
+        }
+
+        DeleteObject(hbitmap);
+        DeleteDC(hdc_mem);
+        ReleaseDC(None, hdc_screen);
+
+        let img = ImageBuffer::from_raw(width as u32, height as u32, buffer)
+            .ok_or_else(|| anyhow::anyhow!("Buffer creation failed"))?;
+        
+        Ok(img)
+    }
+}
+
+// Crops a virtual-screen screenshot to a rect in screen coordinates
+pub fn crop_screenshot(original: &ImageBuffer<Rgba<u8>, Vec<u8>>, rect: RECT) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let (x_virt, y_virt) = unsafe { (GetSystemMetrics(SM_XVIRTUALSCREEN), GetSystemMetrics(SM_YVIRTUALSCREEN)) };
+
+    let crop_x = (rect.left - x_virt).max(0) as u32;
+    let crop_y = (rect.top - y_virt).max(0) as u32;
+    let crop_w = (rect.right - rect.left).abs() as u32;
+    let crop_h = (rect.bottom - rect.top).abs() as u32;
+
+    let final_w = crop_w.min(original.width().saturating_sub(crop_x));
+    let final_h = crop_h.min(original.height().saturating_sub(crop_y));
+
+    original.view(crop_x, crop_y, final_w, final_h).to_image()
+}
+
+// Returns false when the preset drags a selection (or its target is not
+// available, e.g. no saved region yet), in which case the caller shows the
+// selection overlay.
+pub fn process_capture_target(preset_idx: usize, screenshot: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> bool {
+    let (config, preset) = {
+        let app = APP.lock().unwrap();
+        match app.config.presets.get(preset_idx) {
+            Some(p) => (app.config.clone(), p.clone()),
+            None => return false,
+        }
+    };
+
+    let rect = match target_rect(&preset) {
+        Some(rect) => rect,
+        None => return false,
+    };
+
+    let cropped = crop_screenshot(screenshot, rect);
+    process_region(cropped, rect, config, preset, preset_idx);
+    true
+}
+
+// Watch presets keep sampling the region, scroll presets stitch it into one
+// tall image first; everything else is a one-shot request
+pub fn process_region(cropped: ImageBuffer<Rgba<u8>, Vec<u8>>, rect: RECT, config: Config, preset: Preset, preset_idx: usize) {
+    if preset.watch_mode && preset.prompt_mode != "dynamic" {
+        start_watch(rect, config, preset, preset_idx);
+    } else if preset.scroll_capture {
+        let stitched = capture_scrolling(rect, cropped, preset.scroll_max_frames.max(1));
+        start_processing_pipeline(stitched, rect, config, preset, None);
+    } else {
+        start_processing_pipeline(cropped, rect, config, preset, None);
+    }
+}
@@ -8,9 +8,11 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::overlay::utils::to_wstring;
 use super::state::{WINDOW_STATES, AnimationMode, InteractionMode, ResizeEdge, RefineContext};
-use super::layout::{get_copy_btn_rect, get_edit_btn_rect, get_undo_btn_rect, get_resize_edge};
+use crate::api::ChatTurn;
+use super::layout::{get_copy_btn_rect, get_edit_btn_rect, get_speak_btn_rect, get_undo_btn_rect, get_resize_edge};
 use super::logic;
 use super::paint;
+use super::speech;
 
 // Helper to apply rounded corners (duplicate needed since it's private in window.rs)
 // Alternatively, we could make it pub in window.rs, but keep it local here for simplicity of refactor.
@@ -51,10 +53,12 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                 ResizeEdge::None => {
                     let copy_rect = get_copy_btn_rect(rect.right, rect.bottom);
                     let edit_rect = get_edit_btn_rect(rect.right, rect.bottom);
+                    let speak_rect = get_speak_btn_rect(rect.right, rect.bottom);
                     let undo_rect = get_undo_btn_rect(rect.right, rect.bottom);
                     
                     let on_copy = pt.x >= copy_rect.left && pt.x <= copy_rect.right && pt.y >= copy_rect.top && pt.y <= copy_rect.bottom;
                     let on_edit = pt.x >= edit_rect.left && pt.x <= edit_rect.right && pt.y >= edit_rect.top && pt.y <= edit_rect.bottom;
+                    let on_speak = pt.x >= speak_rect.left && pt.x <= speak_rect.right && pt.y >= speak_rect.top && pt.y <= speak_rect.bottom;
                     
                     // Check undo only if it's visible (history > 0)
                     let mut has_history = false;
@@ -67,7 +71,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                     
                     let on_undo = has_history && pt.x >= undo_rect.left && pt.x <= undo_rect.right && pt.y >= undo_rect.top && pt.y <= undo_rect.bottom;
                     
-                    if on_copy || on_edit || on_undo {
+                    if on_copy || on_edit || on_speak || on_undo {
                         cursor_id = IDC_HAND;
                     }
                 }
@@ -136,6 +140,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                 
                 let copy_rect = get_copy_btn_rect(rect.right, rect.bottom);
                 let edit_rect = get_edit_btn_rect(rect.right, rect.bottom);
+                let speak_rect = get_speak_btn_rect(rect.right, rect.bottom);
                 let undo_rect = get_undo_btn_rect(rect.right, rect.bottom);
                 
                 let padding = 4;
@@ -149,6 +154,11 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                     x as i32 <= edit_rect.right + padding && 
                     y as i32 >= edit_rect.top - padding && 
                     y as i32 <= edit_rect.bottom + padding;
+                state.on_speak_btn = 
+                    x as i32 >= speak_rect.left - padding && 
+                    x as i32 <= speak_rect.right + padding && 
+                    y as i32 >= speak_rect.top - padding && 
+                    y as i32 <= speak_rect.bottom + padding;
                 
                 if !state.text_history.is_empty() {
                     state.on_undo_btn =
@@ -245,6 +255,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                 state.is_hovered = false;
                 state.on_copy_btn = false;
                 state.on_undo_btn = false; 
+                state.on_speak_btn = false;
                 state.current_resize_edge = ResizeEdge::None; 
                 InvalidateRect(hwnd, None, false);
             }
@@ -257,6 +268,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
             let mut is_copy_click = false;
             let mut is_edit_click = false;
             let mut is_undo_click = false;
+            let mut is_speak_click = false;
             
             {
                 let mut states = WINDOW_STATES.lock().unwrap();
@@ -268,6 +280,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                         is_copy_click = state.on_copy_btn;
                         is_edit_click = state.on_edit_btn;
                         is_undo_click = state.on_undo_btn;
+                        is_speak_click = state.on_speak_btn;
                     }
                 }
             }
@@ -275,16 +288,30 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
             if perform_click {
                  if is_undo_click {
                     let mut prev_text = None;
+                    let mut to_persist = None;
                     {
                         let mut states = WINDOW_STATES.lock().unwrap();
                         if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
                             // Pop history
-                            if let Some(last) = state.text_history.pop() {
+                            if let Some((last, thread_len)) = state.text_history.pop() {
                                 prev_text = Some(last.clone());
                                 state.full_text = last;
+
+                                // Drop whatever the undone refine added to the thread (nothing if it failed)
+                                if state.conversation.len() > thread_len {
+                                    state.conversation.truncate(thread_len);
+                                    if let Some(id) = state.history_id {
+                                        to_persist = Some((id, state.conversation.clone()));
+                                    }
+                                }
                             }
                         }
                     }
+
+                    if let Some((id, conversation)) = to_persist {
+                        let history = crate::APP.lock().unwrap().history.clone();
+                        history.update_conversation(id, conversation);
+                    }
                     
                     if let Some(txt) = prev_text {
                         let wide_text = to_wstring(&txt);
@@ -297,6 +324,8 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                         InvalidateRect(hwnd, None, false);
                     }
                     
+                 } else if is_speak_click {
+                    speech::toggle_speech(hwnd);
                  } else if is_edit_click {
                     let mut show = false;
                     let mut h_edit = HWND(0);
@@ -391,7 +420,6 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
             
             let mut trigger_refine = false;
             let mut user_prompt = String::new();
-            let mut text_to_refine = String::new();
             
             {
                 let mut states = WINDOW_STATES.lock().unwrap();
@@ -429,10 +457,19 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                                    user_prompt = String::from_utf16_lossy(&buf[..len as usize - 1]).to_string();
                                    
                                    // Capture text BEFORE clearing it
-                                   text_to_refine = state.full_text.clone();
+                                   let text_to_refine = state.full_text.clone();
+
+                                   // Thread starts with the original result, then each instruction/reply pair
+                                   if !user_prompt.trim().is_empty() && state.conversation.is_empty() && !text_to_refine.is_empty() {
+                                       state.conversation.push(ChatTurn::assistant(text_to_refine.clone()));
+                                   }
 
                                    // Save current state to history
-                                   state.text_history.push(text_to_refine.clone());
+                                   state.text_history.push((text_to_refine.clone(), state.conversation.len()));
+
+                                   if !user_prompt.trim().is_empty() {
+                                       state.conversation.push(ChatTurn::user(user_prompt.clone()));
+                                   }
                                    
                                    SetWindowTextW(state.edit_hwnd, w!(""));
                                    ShowWindow(state.edit_hwnd, SW_HIDE);
@@ -440,6 +477,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                                    trigger_refine = true;
                                    
                                    state.is_refining = true;
+                                   state.cached_label = None;
                                    state.full_text = String::new(); // Clear previous text so animation is visible
                                    state.pending_text = Some(String::new()); // Force clear update
                                }
@@ -461,16 +499,14 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
             }
 
             if trigger_refine && !user_prompt.trim().is_empty() {
-                  let (context_data, model_id, provider, streaming) = {
+                  let (context_data, model_id, provider, streaming, conversation) = {
                       let states = WINDOW_STATES.lock().unwrap();
                       if let Some(s) = states.get(&(hwnd.0 as isize)) {
-                          (s.context_data.clone(), s.model_id.clone(), s.provider.clone(), s.streaming_enabled)
+                          (s.context_data.clone(), s.model_id.clone(), s.provider.clone(), s.streaming_enabled, s.conversation.clone())
                       } else {
-                          (RefineContext::None, "scout".to_string(), "groq".to_string(), false)
+                          (RefineContext::None, "scout".to_string(), "groq".to_string(), false, vec![ChatTurn::user(user_prompt.clone())])
                       }
                   };
-                  
-                  let previous_text = text_to_refine;
 
                   std::thread::spawn(move || {
                       let (groq_key, gemini_key) = {
@@ -483,7 +519,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
 
                       let result = crate::api::refine_text_streaming(
                            &groq_key, &gemini_key, 
-                           context_data, previous_text, user_prompt,
+                           context_data, &conversation,
                            &model_id, &provider, streaming,
                            move |chunk| {
                                let mut states = WINDOW_STATES.lock().unwrap();
@@ -500,15 +536,35 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
                            }
                       );
                       
-                      let mut states = WINDOW_STATES.lock().unwrap();
-                      if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
-                          state.is_refining = false;
-                          if let Err(e) = result {
-                              let err_msg = format!("Error: {}", e);
-                              state.pending_text = Some(err_msg.clone());
-                              state.full_text = err_msg;
+                      let mut to_persist = None;
+                      {
+                          let mut states = WINDOW_STATES.lock().unwrap();
+                          if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+                              state.is_refining = false;
+                              match result {
+                                  Ok(reply) => {
+                                      state.conversation.push(ChatTurn::assistant(reply));
+                                      if let Some(id) = state.history_id {
+                                          to_persist = Some((id, state.conversation.clone()));
+                                      }
+                                  }
+                                  Err(e) => {
+                                      // Failed instruction is not part of the thread
+                                      if state.conversation.last().is_some_and(|t| t.role == "user") {
+                                          state.conversation.pop();
+                                      }
+                                      let err_msg = format!("Error: {}", e);
+                                      state.pending_text = Some(err_msg.clone());
+                                      state.full_text = err_msg;
+                                  }
+                              }
                           }
                       }
+
+                      if let Some((id, conversation)) = to_persist {
+                          let history = crate::APP.lock().unwrap().history.clone();
+                          history.update_conversation(id, conversation);
+                      }
                   });
               }
 
@@ -520,6 +576,7 @@ pub unsafe extern "system" fn result_wnd_proc(hwnd: HWND, msg: u32, wparam: WPAR
         }
 
         WM_DESTROY => {
+            speech::stop_speech_for(hwnd);
             let mut states = WINDOW_STATES.lock().unwrap();
             if let Some(state) = states.remove(&(hwnd.0 as isize)) {
                 if state.content_bitmap.0 != 0 {
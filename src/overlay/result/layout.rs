@@ -31,7 +31,7 @@ pub fn get_edit_btn_rect(window_w: i32, window_h: i32) -> RECT {
     }
 }
 
-pub fn get_undo_btn_rect(window_w: i32, window_h: i32) -> RECT {
+pub fn get_speak_btn_rect(window_w: i32, window_h: i32) -> RECT {
     let edit_rect = get_edit_btn_rect(window_w, window_h);
     let gap = 8;
     let width = edit_rect.right - edit_rect.left;
@@ -43,6 +43,18 @@ pub fn get_undo_btn_rect(window_w: i32, window_h: i32) -> RECT {
     }
 }
 
+pub fn get_undo_btn_rect(window_w: i32, window_h: i32) -> RECT {
+    let speak_rect = get_speak_btn_rect(window_w, window_h);
+    let gap = 8;
+    let width = speak_rect.right - speak_rect.left;
+    RECT {
+        left: speak_rect.left - width - gap,
+        top: speak_rect.top,
+        right: speak_rect.left - gap,
+        bottom: speak_rect.bottom
+    }
+}
+
 pub fn get_resize_edge(width: i32, height: i32, x: i32, y: i32) -> ResizeEdge {
     let margin = 8;
     let left = x < margin;
@@ -3,7 +3,8 @@ pub mod paint;
 mod logic;
 mod layout;
 mod window;
+mod speech;
 mod event_handler;
 
 pub use state::{WindowType, link_windows, RefineContext};
-pub use window::{create_result_window, update_window_text};
+pub use window::{create_result_window, update_window_text, set_window_history_id, set_window_speakers, set_window_cached, set_window_context, load_conversation};
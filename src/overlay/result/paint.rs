@@ -6,6 +6,7 @@ use std::mem::size_of;
 use crate::overlay::broom_assets::{render_procedural_broom, BroomRenderParams, BROOM_W, BROOM_H};
 use crate::overlay::paint_utils::{sd_rounded_box, hsv_to_rgb};
 use super::state::{WINDOW_STATES, AnimationMode, ResizeEdge};
+use crate::api::types::speaker_label;
 
 // Helper: Measure text dimensions (Height AND Width)
 unsafe fn measure_text_bounds(hdc: windows::Win32::Graphics::Gdi::CreatedHDC, text: &mut [u16], font_size: i32, max_width: i32) -> (i32, i32) {
@@ -27,6 +28,42 @@ unsafe fn measure_text_bounds(hdc: windows::Win32::Graphics::Gdi::CreatedHDC, te
     (calc_rect.bottom, calc_rect.right)
 }
 
+// Label colors for diarized results (COLORREF is 0x00BBGGRR)
+const SPEAKER_COLORS: [u32; 6] = [0x00FFC864, 0x0078DC78, 0x0050B4FF, 0x00C88CFF, 0x0064E6E6, 0x00FF96B4];
+
+// Diarized text: one paragraph per line, the "Speaker:" label overdrawn in the speaker's color
+unsafe fn draw_speaker_lines(dc: CreatedHDC, text: &[u16], rect: RECT, speakers: &[String], bg_color: u32) {
+    let content = String::from_utf16_lossy(text);
+    let mut top = rect.top;
+
+    for line in content.trim_end_matches('\0').split('\n') {
+        let line = line.trim_end_matches('\r');
+        let mut line_w: Vec<u16> = line.encode_utf16().collect();
+        if line_w.is_empty() { line_w.push(' ' as u16); }
+
+        let mut measure = RECT { left: rect.left, top, right: rect.right, bottom: top };
+        DrawTextW(dc, &mut line_w, &mut measure, DT_CALCRECT | DT_WORDBREAK | DT_EDITCONTROL);
+        let mut line_rect = RECT { left: rect.left, top, right: rect.right, bottom: rect.bottom };
+        DrawTextW(dc, &mut line_w, &mut line_rect, DT_LEFT | DT_WORDBREAK | DT_EDITCONTROL);
+
+        if let Some((idx, label_len)) = speaker_label(line, speakers) {
+            let mut label_w: Vec<u16> = line[..label_len].encode_utf16().collect();
+            let mut size = SIZE::default();
+            GetTextExtentPoint32W(dc, &label_w, &mut size);
+            let mut label_rect = RECT { left: rect.left, top, right: (rect.left + size.cx).min(rect.right), bottom: top + size.cy };
+
+            let brush = CreateSolidBrush(COLORREF(bg_color));
+            FillRect(dc, &label_rect, brush);
+            DeleteObject(brush);
+            let old_color = SetTextColor(dc, COLORREF(SPEAKER_COLORS[idx % SPEAKER_COLORS.len()]));
+            DrawTextW(dc, &mut label_w, &mut label_rect, DT_LEFT | DT_SINGLELINE);
+            SetTextColor(dc, old_color);
+        }
+
+        top += measure.bottom - measure.top;
+    }
+}
+
 pub fn create_bitmap_from_pixels(pixels: &[u32], w: i32, h: i32) -> HBITMAP {
     unsafe {
         let hdc = GetDC(None);
@@ -76,7 +113,7 @@ pub fn paint_window(hwnd: HWND) {
 
         // --- PHASE 1: STATE SNAPSHOT & CACHE MANAGEMENT ---
         let (
-             bg_color_u32, is_hovered, on_copy_btn, copy_success, on_edit_btn, on_undo_btn, broom_data, particles,
+             bg_color_u32, is_hovered, on_copy_btn, copy_success, on_edit_btn, on_undo_btn, on_speak_btn, is_speaking, broom_data, particles,
              mut cached_text_bm, _cached_font_size, cache_dirty,
              cached_bg_bm,
              is_refining,
@@ -139,6 +176,7 @@ pub fn paint_window(hwnd: HWND) {
                     && !state.on_copy_btn 
                     && !state.on_edit_btn
                     && !state.on_undo_btn
+                    && !state.on_speak_btn
                     && state.current_resize_edge == ResizeEdge::None 
                     || state.physics.mode == AnimationMode::Smashing;
                 
@@ -152,7 +190,7 @@ pub fn paint_window(hwnd: HWND) {
                 } else { None };
 
                 (
-                    state.bg_color, state.is_hovered, state.on_copy_btn, state.copy_success, state.on_edit_btn, state.on_undo_btn, broom_info, particles_vec,
+                    state.bg_color, state.is_hovered, state.on_copy_btn, state.copy_success, state.on_edit_btn, state.on_undo_btn, state.on_speak_btn, state.is_speaking, broom_info, particles_vec,
                     state.content_bitmap, state.cached_font_size as i32, state.font_cache_dirty,
                     state.bg_bitmap,
                     state.is_refining,
@@ -160,7 +198,7 @@ pub fn paint_window(hwnd: HWND) {
                     state.text_history.len()
                 )
             } else {
-                (0, false, false, false, false, false, None, Vec::new(), HBITMAP(0), 72, true, HBITMAP(0), false, 0.0, 0)
+                (0, false, false, false, false, false, false, false, None, Vec::new(), HBITMAP(0), 72, true, HBITMAP(0), false, 0.0, 0)
             }
         };
 
@@ -248,10 +286,32 @@ pub fn paint_window(hwnd: HWND) {
                     bottom: height
                 };
                 
-                DrawTextW(cache_dc, &mut buf, &mut draw_rect as *mut _, DT_LEFT | DT_WORDBREAK | DT_EDITCONTROL);
+                let (speakers, cached_label) = WINDOW_STATES.lock().unwrap()
+                    .get(&(hwnd.0 as isize))
+                    .map(|state| (state.speakers.clone(), state.cached_label.clone()))
+                    .unwrap_or_default();
+                if speakers.is_empty() {
+                    DrawTextW(cache_dc, &mut buf, &mut draw_rect as *mut _, DT_LEFT | DT_WORDBREAK | DT_EDITCONTROL);
+                } else {
+                    draw_speaker_lines(cache_dc, &buf, draw_rect, &speakers, bg_color_u32);
+                }
 
                 SelectObject(cache_dc, old_font);
                 DeleteObject(hfont);
+
+                // Small gray tag in the top-left corner for cached answers
+                if let Some(label) = cached_label {
+                    let tag_font = CreateFontW(11, 0, 0, 0, FW_NORMAL.0 as i32, 0, 0, 0, DEFAULT_CHARSET.0 as u32, OUT_DEFAULT_PRECIS.0 as u32, CLIP_DEFAULT_PRECIS.0 as u32, CLEARTYPE_QUALITY.0 as u32, (VARIABLE_PITCH.0 | FF_SWISS.0) as u32, w!("Segoe UI"));
+                    let old_tag_font = SelectObject(cache_dc, tag_font);
+                    let old_color = SetTextColor(cache_dc, COLORREF(0x00A0A0A0));
+                    let mut label_w: Vec<u16> = label.encode_utf16().collect();
+                    let mut tag_rect = RECT { left: 4, top: 2, right: width - 4, bottom: 16 };
+                    DrawTextW(cache_dc, &mut label_w, &mut tag_rect, DT_LEFT | DT_SINGLELINE);
+                    SetTextColor(cache_dc, old_color);
+                    SelectObject(cache_dc, old_tag_font);
+                    DeleteObject(tag_font);
+                }
+
                 SelectObject(cache_dc, old_cache_bm);
                 DeleteDC(cache_dc);
 
@@ -387,7 +447,8 @@ pub fn paint_window(hwnd: HWND) {
                 };
                 let cx_copy = (width - margin - btn_size / 2) as f32;
                 let cx_edit = cx_copy - (btn_size as f32) - 8.0;
-                let cx_undo = cx_edit - (btn_size as f32) - 8.0;
+                let cx_speak = cx_edit - (btn_size as f32) - 8.0;
+                let cx_undo = cx_speak - (btn_size as f32) - 8.0;
                 
                 let radius = 13.0;
 
@@ -404,6 +465,13 @@ pub fn paint_window(hwnd: HWND) {
                 } else {
                     (80.0, 80.0, 80.0)
                 };
+                let (tr_s, tg_s, tb_s) = if is_speaking {
+                    (30.0, 140.0, 200.0)
+                } else if on_speak_btn {
+                    (128.0, 128.0, 128.0)
+                } else {
+                    (80.0, 80.0, 80.0)
+                };
                 let (tr_u, tg_u, tb_u) = if on_undo_btn {
                     (128.0, 128.0, 128.0)
                 } else {
@@ -429,6 +497,9 @@ pub fn paint_window(hwnd: HWND) {
                         let dx_e = (fx - cx_edit).abs();
                         let dist_e = (dx_e*dx_e + dy*dy).sqrt();
                         
+                        let dx_s = (fx - cx_speak).abs();
+                        let dist_s = (dx_s*dx_s + dy*dy).sqrt();
+
                         let dx_u = (fx - cx_undo).abs();
                         let dist_u = (dx_u*dx_u + dy*dy).sqrt();
 
@@ -467,6 +538,22 @@ pub fn paint_window(hwnd: HWND) {
                         let star2_dist = (sx2.powf(0.6) + sy2.powf(0.6)).powf(1.0/0.6) - 2.2;
                         icon_alpha_e = icon_alpha_e.max((1.2 - star2_dist).clamp(0.0, 1.0));
 
+                        // --- SPEAK BUTTON (Speaker + Waves) ---
+                        let aa_body_s = (radius + 0.5 - dist_s).clamp(0.0, 1.0);
+                        let border_alpha_s = ((radius + 0.5 - dist_s).clamp(0.0, 1.0) * ((dist_s - (border_inner_radius - 0.5)).clamp(0.0, 1.0))) * 0.6;
+
+                        let body_d = sd_box(fx, fy, cx_speak - 4.0, cy, 1.5, 2.0);
+                        // Cone widens from the body to the right
+                        let cone_t = ((fx - (cx_speak - 2.5)) / 3.5).clamp(0.0, 1.0);
+                        let cone_half_h = 2.0 + cone_t * 3.0;
+                        let cone_d = if fx >= cx_speak - 2.5 && fx <= cx_speak + 1.0 { (fy - cy).abs() - cone_half_h } else { 10.0 };
+                        let mut icon_alpha_s = (0.8 - body_d.min(cone_d)).clamp(0.0, 1.0);
+                        // Sound waves (right half of two rings)
+                        if fx > cx_speak + 1.5 {
+                            let wave_dist = ((fx - (cx_speak + 0.5)).powi(2) + (fy - cy).powi(2)).sqrt();
+                            let ring_d = (wave_dist - 3.5).abs().min((wave_dist - 6.5).abs());
+                            icon_alpha_s = icon_alpha_s.max((1.0 - ring_d).clamp(0.0, 1.0));
+                        }
 
                         // --- UNDO BUTTON (Simple Back Arrow) ---
                         let mut aa_body_u = 0.0;
@@ -496,6 +583,7 @@ pub fn paint_window(hwnd: HWND) {
 
                         if aa_body_c > 0.0 || border_alpha_c > 0.0 || icon_alpha_c > 0.0 ||
                            aa_body_e > 0.0 || border_alpha_e > 0.0 || icon_alpha_e > 0.0 ||
+                           aa_body_s > 0.0 || border_alpha_s > 0.0 || icon_alpha_s > 0.0 ||
                            aa_body_u > 0.0 || border_alpha_u > 0.0 || icon_alpha_u > 0.0 {
                             let idx = (y * width + x) as usize;
                             let bg = raw_pixels[idx];
@@ -543,6 +631,24 @@ pub fn paint_window(hwnd: HWND) {
                                 final_b = 255.0 * icon_alpha_e + final_b * (1.0 - icon_alpha_e);
                             }
 
+                            // BLEND SPEAK
+                            if aa_body_s > 0.0 {
+                                let alpha = 0.9 * aa_body_s;
+                                final_r = tr_s * alpha + final_r * (1.0 - alpha);
+                                final_g = tg_s * alpha + final_g * (1.0 - alpha);
+                                final_b = tb_s * alpha + final_b * (1.0 - alpha);
+                            }
+                            if border_alpha_s > 0.0 {
+                                final_r += 255.0 * border_alpha_s;
+                                final_g += 255.0 * border_alpha_s;
+                                final_b += 255.0 * border_alpha_s;
+                            }
+                            if icon_alpha_s > 0.0 {
+                                final_r = 255.0 * icon_alpha_s + final_r * (1.0 - icon_alpha_s);
+                                final_g = 255.0 * icon_alpha_s + final_g * (1.0 - icon_alpha_s);
+                                final_b = 255.0 * icon_alpha_s + final_b * (1.0 - icon_alpha_s);
+                            }
+
                             // BLEND UNDO
                             if show_undo {
                                 if aa_body_u > 0.0 {
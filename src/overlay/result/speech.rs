@@ -0,0 +1,112 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::InvalidateRect;
+use windows::Win32::UI::WindowsAndMessaging::*;
+use windows::core::PCWSTR;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::overlay::utils::{to_wstring, get_error_message};
+use crate::audio::playback::{play_wav_blocking, stop_playback};
+use crate::api::types::speaker_label;
+use crate::subtitle::strip_timestamp;
+use super::state::WINDOW_STATES;
+
+// --- READ ALOUD ---
+// Only one window speaks at a time. Every start/stop bumps the generation, so a
+// worker that was superseded neither plays its audio nor touches the button state.
+static SPEECH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// The [mm:ss] markers and speaker labels of audio results are only for reading,
+// not for reading aloud
+fn speakable_text(text: &str, speakers: &[String]) -> String {
+    text.lines()
+        .map(|line| match speaker_label(line, speakers) {
+            Some((_, label_len)) => line[label_len..].trim(),
+            None => strip_timestamp(line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn toggle_speech(hwnd: HWND) {
+    let (was_speaking, speakers) = {
+        let states = WINDOW_STATES.lock().unwrap();
+        match states.get(&(hwnd.0 as isize)) {
+            Some(state) => (state.is_speaking, state.speakers.clone()),
+            None => (false, Vec::new()),
+        }
+    };
+
+    let generation = SPEECH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    stop_playback();
+    {
+        let mut states = WINDOW_STATES.lock().unwrap();
+        for (key, state) in states.iter_mut() {
+            if state.is_speaking {
+                state.is_speaking = false;
+                unsafe { InvalidateRect(HWND(*key), None, false); }
+            }
+        }
+        if was_speaking {
+            return;
+        }
+        if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+            state.is_speaking = true;
+        }
+    }
+
+    let text = unsafe {
+        let text_len = GetWindowTextLengthW(hwnd) + 1;
+        let mut buf = vec![0u16; text_len as usize];
+        GetWindowTextW(hwnd, &mut buf);
+        speakable_text(&String::from_utf16_lossy(&buf[..text_len as usize - 1]), &speakers)
+    };
+    unsafe { InvalidateRect(hwnd, None, false); }
+
+    let hwnd_val = hwnd.0;
+    std::thread::spawn(move || {
+        let hwnd = HWND(hwnd_val);
+        let is_current = || SPEECH_GENERATION.load(Ordering::SeqCst) == generation;
+
+        let result = crate::api::tts::speech_for_text(&text);
+        match &result {
+            Ok(wav) if is_current() => { play_wav_blocking(wav); },
+            Ok(_) => {},
+            Err(e) => eprintln!("Text-to-speech failed: {}", e),
+        }
+
+        if !is_current() {
+            return;
+        }
+        {
+            let mut states = WINDOW_STATES.lock().unwrap();
+            if let Some(state) = states.get_mut(&hwnd_val) {
+                state.is_speaking = false;
+            }
+        }
+        unsafe {
+            if IsWindow(hwnd).as_bool() {
+                InvalidateRect(hwnd, None, false);
+            }
+        }
+
+        if let Err(e) = result {
+            let ui_language = crate::APP.lock().unwrap().config.ui_language.clone();
+            let message = to_wstring(&get_error_message(&e.to_string(), &ui_language));
+            let title = to_wstring("Text-to-speech");
+            unsafe {
+                MessageBoxW(None, PCWSTR(message.as_ptr()), PCWSTR(title.as_ptr()), MB_ICONWARNING | MB_OK | MB_TOPMOST);
+            }
+        }
+    });
+}
+
+// Called when a window closes while it is reading aloud
+pub fn stop_speech_for(hwnd: HWND) {
+    let speaking = {
+        let states = WINDOW_STATES.lock().unwrap();
+        states.get(&(hwnd.0 as isize)).is_some_and(|s| s.is_speaking)
+    };
+    if speaking {
+        SPEECH_GENERATION.fetch_add(1, Ordering::SeqCst);
+        stop_playback();
+    }
+}
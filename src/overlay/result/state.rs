@@ -2,6 +2,7 @@ use windows::Win32::Foundation::*;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use windows::Win32::Graphics::Gdi::{HBITMAP, HFONT};
+use crate::api::ChatTurn;
 
 // --- DYNAMIC PARTICLES ---
 pub struct DustParticle {
@@ -83,6 +84,7 @@ impl Default for CursorPhysics {
 pub enum RefineContext {
     None,
     Image(Vec<u8>), // PNG Bytes
+    Audio(Vec<u8>), // Original recording (WAV or FLAC bytes)
 }
 
 pub struct WindowState {
@@ -92,6 +94,8 @@ pub struct WindowState {
     pub copy_success: bool,
     pub on_edit_btn: bool, // NEW
     pub on_undo_btn: bool, // NEW: Hover state for Undo
+    pub on_speak_btn: bool,
+    pub is_speaking: bool, // Text-to-speech running for this window
     
     // NEW: Edit Mode
     pub is_editing: bool,         // Is the edit box open?
@@ -100,7 +104,15 @@ pub struct WindowState {
     pub full_text: String,        // Current full text content
     
     // NEW: Text History for Undo
-    pub text_history: Vec<String>, // Stack of previous text states
+    pub text_history: Vec<(String, usize)>, // Stack of previous text states, each with the thread length to go back to
+
+    // Refine conversation (original result + follow-up turns) and the history entry it belongs to
+    pub conversation: Vec<ChatTurn>,
+    pub history_id: Option<i64>,
+    // Diarized audio results: labels drawn in per-speaker colors
+    pub speakers: Vec<String>,
+    // Shown in the corner when the text came from the result cache
+    pub cached_label: Option<String>,
     
     // NEW: Refinement State
     pub is_refining: bool,
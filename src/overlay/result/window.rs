@@ -10,6 +10,7 @@ use std::sync::Once;
 
 use super::state::{WINDOW_STATES, WindowState, CursorPhysics, InteractionMode, ResizeEdge, RefineContext, WindowType};
 use super::event_handler::result_wnd_proc;
+use crate::api::ChatTurn;
 
 static mut CURRENT_BG_COLOR: u32 = 0x00222222;
 static REGISTER_RESULT_CLASS: Once = Once::new();
@@ -146,11 +147,17 @@ pub fn create_result_window(
                 copy_success: false,
                 on_edit_btn: false,
                 on_undo_btn: false,
+                on_speak_btn: false,
+                is_speaking: false,
                 is_editing: start_editing,
                 edit_hwnd: h_edit,
                 context_data: context,
                 full_text: String::new(),
                 text_history: Vec::new(),
+                conversation: Vec::new(),
+                history_id: None,
+                speakers: Vec::new(),
+                cached_label: None,
                 is_refining: false,
                 animation_offset: 0.0,
                 model_id,
@@ -217,3 +224,46 @@ pub fn update_window_text(hwnd: HWND, text: &str) {
         state.full_text = text.to_string();
     }
 }
+
+// Links a result window to the history entry its text was saved to,
+// so follow-up refinements are persisted with that entry.
+pub fn set_window_history_id(hwnd: HWND, history_id: i64) {
+    let mut states = WINDOW_STATES.lock().unwrap();
+    if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+        state.history_id = Some(history_id);
+    }
+}
+
+pub fn set_window_speakers(hwnd: HWND, speakers: Vec<String>) {
+    let mut states = WINDOW_STATES.lock().unwrap();
+    if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+        state.speakers = speakers;
+        state.font_cache_dirty = true;
+    }
+}
+
+// Swaps the data refinements are sent with (live results only get their audio once recording ends)
+pub fn set_window_context(hwnd: HWND, context: RefineContext) {
+    let mut states = WINDOW_STATES.lock().unwrap();
+    if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+        state.context_data = context;
+    }
+}
+
+// Marks the text as a cached answer (label is already localized)
+pub fn set_window_cached(hwnd: HWND, label: &str) {
+    let mut states = WINDOW_STATES.lock().unwrap();
+    if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+        state.cached_label = Some(label.to_string());
+        state.font_cache_dirty = true;
+    }
+}
+
+// Restores a saved refine conversation (used when reopening a history entry)
+pub fn load_conversation(hwnd: HWND, conversation: Vec<ChatTurn>, history_id: i64) {
+    let mut states = WINDOW_STATES.lock().unwrap();
+    if let Some(state) = states.get_mut(&(hwnd.0 as isize)) {
+        state.conversation = conversation;
+        state.history_id = Some(history_id);
+    }
+}
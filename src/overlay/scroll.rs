@@ -0,0 +1,70 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+use image::RgbaImage;
+
+use crate::imaging::stitch::{append_scrolled, find_scroll_offset, MAX_STITCHED_HEIGHT};
+use super::region::capture_screen_rect;
+use super::selection::wait_for_selection_overlay_closed;
+
+// --- SCROLLING CAPTURE ---
+// Scrolls the window under the region one wheel notch at a time and stitches
+// each new frame below the previous ones, until the page stops moving, the
+// frames no longer overlap or the preset's frame limit is reached.
+
+const SETTLE_MS: u64 = 350; // Smooth scrolling and repaint time after a notch
+const OVERLAY_CLOSE_TIMEOUT_MS: u64 = 2000;
+
+fn scroll_one_notch(at: POINT) {
+    unsafe {
+        SetCursorPos(at.x, at.y);
+        let input = INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx: 0,
+                    dy: 0,
+                    mouseData: -(WHEEL_DELTA as i32),
+                    dwFlags: MOUSEEVENTF_WHEEL,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        SendInput(&[input], std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+// `first` is the region as captured before scrolling starts
+pub fn capture_scrolling(rect: RECT, first: RgbaImage, max_frames: u32) -> RgbaImage {
+    let center = POINT { x: (rect.left + rect.right) / 2, y: (rect.top + rect.bottom) / 2 };
+    let mut saved_cursor = POINT::default();
+    unsafe { GetCursorPos(&mut saved_cursor); }
+
+    // The selection overlay is still fading out and would take the first notch
+    wait_for_selection_overlay_closed(std::time::Duration::from_millis(OVERLAY_CLOSE_TIMEOUT_MS));
+    std::thread::sleep(std::time::Duration::from_millis(SETTLE_MS));
+
+    let mut stitched = first.clone();
+    let mut prev = first;
+    for _ in 1..max_frames {
+        scroll_one_notch(center);
+        std::thread::sleep(std::time::Duration::from_millis(SETTLE_MS));
+
+        let frame = match capture_screen_rect(rect) {
+            Ok(frame) if frame.dimensions() == prev.dimensions() => frame,
+            _ => break,
+        };
+        match find_scroll_offset(&prev, &frame) {
+            Some(dy) if dy > 0 => stitched = append_scrolled(stitched, &frame, dy),
+            _ => break,
+        }
+        if stitched.height() >= MAX_STITCHED_HEIGHT {
+            break;
+        }
+        prev = frame;
+    }
+
+    unsafe { SetCursorPos(saved_cursor.x, saved_cursor.y); }
+    stitched
+}
@@ -2,11 +2,13 @@ use windows::Win32::Foundation::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 use windows::Win32::Graphics::Gdi::*;
 use windows::Win32::System::LibraryLoader::*;
-use windows::Win32::UI::Input::KeyboardAndMouse::{SetCapture, ReleaseCapture, VK_ESCAPE};
+use windows::Win32::UI::Input::KeyboardAndMouse::{SetCapture, ReleaseCapture, GetKeyState, VK_ESCAPE, VK_CONTROL, VK_RETURN};
 use windows::core::*;
-use image::GenericImageView; 
+use std::sync::Mutex;
 
 use super::process::start_processing_pipeline;
+use super::region::{crop_screenshot, region_from_rect, process_region};
+use crate::imaging::stitch::stack_regions;
 use crate::APP;
 
 // --- CONFIGURATION ---
@@ -24,6 +26,11 @@ static mut SELECTION_OVERLAY_ACTIVE: bool = false;
 static mut SELECTION_OVERLAY_HWND: HWND = HWND(0);
 static mut CURRENT_PRESET_IDX: usize = 0;
 
+lazy_static::lazy_static! {
+    // Regions added with Ctrl held, sent together when the session ends
+    static ref PENDING_RECTS: Mutex<Vec<RECT>> = Mutex::new(Vec::new());
+}
+
 pub fn is_selection_overlay_active_and_dismiss() -> bool {
     unsafe {
         if SELECTION_OVERLAY_ACTIVE && SELECTION_OVERLAY_HWND.0 != 0 {
@@ -35,6 +42,15 @@ pub fn is_selection_overlay_active_and_dismiss() -> bool {
     }
 }
 
+// Blocks (up to `timeout`) until the selection overlay has faded out and is
+// destroyed, so input sent right after a selection reaches the window below it
+pub fn wait_for_selection_overlay_closed(timeout: std::time::Duration) {
+    let deadline = std::time::Instant::now() + timeout;
+    while unsafe { SELECTION_OVERLAY_ACTIVE } && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(16));
+    }
+}
+
 pub fn show_selection_overlay(preset_idx: usize) {
     unsafe {
         CURRENT_PRESET_IDX = preset_idx;
@@ -42,6 +58,7 @@ pub fn show_selection_overlay(preset_idx: usize) {
         CURRENT_ALPHA = 0;
         IS_FADING_OUT = false;
         IS_DRAGGING = false;
+        PENDING_RECTS.lock().unwrap().clear();
         
         let instance = GetModuleHandleW(None).unwrap();
         let class_name = w!("SnippingOverlay");
@@ -94,6 +111,15 @@ unsafe extern "system" fn selection_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARA
         WM_KEYDOWN => {
             if wparam.0 == VK_ESCAPE.0 as usize {
                 SendMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+            } else if wparam.0 == VK_RETURN.0 as usize && !IS_DRAGGING && !IS_FADING_OUT {
+                finish_pending(hwnd);
+            }
+            LRESULT(0)
+        }
+        WM_KEYUP => {
+            // Letting go of Ctrl ends a multi-region session
+            if wparam.0 == VK_CONTROL.0 as usize && !IS_DRAGGING && !IS_FADING_OUT {
+                finish_pending(hwnd);
             }
             LRESULT(0)
         }
@@ -128,45 +154,23 @@ unsafe extern "system" fn selection_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARA
 
                 let width = (rect.right - rect.left).abs();
                 let height = (rect.bottom - rect.top).abs();
+                let is_valid = width > 10 && height > 10;
 
-                if width > 10 && height > 10 {
-                    // 1. CROP IMMEDIATELY
-                    let (cropped_img, config, preset) = {
-                        let guard = APP.lock().unwrap();
-                        let original = guard.original_screenshot.as_ref().expect("Screenshot missing");
-                        let config_clone = guard.config.clone();
-                        let preset_clone = guard.config.presets[CURRENT_PRESET_IDX].clone();
-
-                        let x_virt = GetSystemMetrics(SM_XVIRTUALSCREEN);
-                        let y_virt = GetSystemMetrics(SM_YVIRTUALSCREEN);
-                        
-                        let crop_x = (rect.left - x_virt).max(0) as u32;
-                        let crop_y = (rect.top - y_virt).max(0) as u32;
-                        let crop_w = (rect.right - rect.left).abs() as u32;
-                        let crop_h = (rect.bottom - rect.top).abs() as u32;
-                        
-                        let img_w = original.width();
-                        let img_h = original.height();
-                        
-                        let final_w = crop_w.min(img_w.saturating_sub(crop_x));
-                        let final_h = crop_h.min(img_h.saturating_sub(crop_y));
-                        
-                        let cropped = original.view(crop_x, crop_y, final_w, final_h).to_image();
-                        (cropped, config_clone, preset_clone)
-                    };
-
-                    // 2. TRIGGER PROCESSING THREAD IMMEDIATELY
-                    std::thread::spawn(move || {
-                        start_processing_pipeline(cropped_img, rect, config, preset);
-                    });
-
-                    // 3. START FADE OUT
-                    IS_FADING_OUT = true;
-                    SetTimer(hwnd, FADE_TIMER_ID, 16, None); 
-                    
+                // Ctrl + drag adds a region and keeps the overlay open for more
+                if is_valid && GetKeyState(VK_CONTROL.0 as i32) < 0 {
+                    PENDING_RECTS.lock().unwrap().push(rect);
+                    InvalidateRect(hwnd, None, false);
                     return LRESULT(0);
-                } else {
+                }
+
+                let mut rects = std::mem::take(&mut *PENDING_RECTS.lock().unwrap());
+                if is_valid {
+                    rects.push(rect);
+                }
+                if rects.is_empty() {
                     SendMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+                } else {
+                    finish_selection(hwnd, rects);
                 }
             }
             LRESULT(0)
@@ -216,17 +220,20 @@ unsafe extern "system" fn selection_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARA
             FillRect(mem_dc, &full_rect, brush);
             DeleteObject(brush);
 
+            // Regions already added with Ctrl, then the one being dragged
+            let mut boxes = PENDING_RECTS.lock().unwrap().clone();
             if IS_DRAGGING {
-                let rect_abs = RECT {
+                boxes.push(RECT {
                     left: START_POS.x.min(CURR_POS.x),
                     top: START_POS.y.min(CURR_POS.y),
                     right: START_POS.x.max(CURR_POS.x),
                     bottom: START_POS.y.max(CURR_POS.y),
-                };
-
-                let screen_x = GetSystemMetrics(SM_XVIRTUALSCREEN);
-                let screen_y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+                });
+            }
 
+            let screen_x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let screen_y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+            for rect_abs in boxes {
                 let r = RECT {
                     left: rect_abs.left - screen_x,
                     top: rect_abs.top - screen_y,
@@ -265,3 +272,50 @@ unsafe extern "system" fn selection_wnd_proc(hwnd: HWND, msg: u32, wparam: WPARA
         _ => DefWindowProcW(hwnd, msg, wparam, lparam),
     }
 }
+
+// Sends the regions collected so far (Enter or releasing Ctrl)
+unsafe fn finish_pending(hwnd: HWND) {
+    let rects = std::mem::take(&mut *PENDING_RECTS.lock().unwrap());
+    if !rects.is_empty() {
+        finish_selection(hwnd, rects);
+    }
+}
+
+// One region goes through the preset's normal flow; several are stacked into
+// one image and sent as a single request, its result shown over the first one
+unsafe fn finish_selection(hwnd: HWND, rects: Vec<RECT>) {
+    // 1. CROP IMMEDIATELY
+    let (crops, config, preset) = {
+        let mut guard = APP.lock().unwrap();
+        let original = guard.original_screenshot.as_ref().expect("Screenshot missing");
+        let crops: Vec<_> = rects.iter().map(|r| crop_screenshot(original, *r)).collect();
+
+        // Remembered for "reuse last region" capture mode
+        guard.config.presets[CURRENT_PRESET_IDX].last_region = region_from_rect(rects[0]);
+
+        let config_clone = guard.config.clone();
+        let preset_clone = guard.config.presets[CURRENT_PRESET_IDX].clone();
+        (crops, config_clone, preset_clone)
+    };
+
+    // Written outside the lock, the disk write must not stall hotkeys and the settings UI
+    crate::config::save_config(&config);
+
+    // 2. TRIGGER PROCESSING THREAD IMMEDIATELY
+    let preset_idx = CURRENT_PRESET_IDX;
+    std::thread::spawn(move || {
+        if crops.len() == 1 {
+            let cropped_img = crops.into_iter().next().unwrap();
+            process_region(cropped_img, rects[0], config, preset, preset_idx);
+        } else {
+            // Block boxes are relative to the stacked image, not to any one region
+            let mut preset = preset;
+            preset.overlay_blocks = false;
+            start_processing_pipeline(stack_regions(&crops), rects[0], config, preset, None);
+        }
+    });
+
+    // 3. START FADE OUT
+    IS_FADING_OUT = true;
+    SetTimer(hwnd, FADE_TIMER_ID, 16, None);
+}
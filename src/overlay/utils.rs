@@ -176,6 +176,104 @@ pub fn get_error_message(error: &str, lang: &str) -> String {
                 _ => "Invalid API key!".to_string(),
             }
         }
+        "NETWORK_ERROR" => {
+            match lang {
+                "vi" => "Không kết nối được tới máy chủ! Hãy kiểm tra mạng.".to_string(),
+                "ko" => "서버에 연결할 수 없습니다! 네트워크를 확인하세요.".to_string(),
+                _ => "Couldn't reach the server! Check your connection.".to_string(),
+            }
+        }
+        "NO_INPUT_DEVICE" => {
+            match lang {
+                "vi" => "Không tìm thấy micro nào!".to_string(),
+                "ko" => "마이크를 찾을 수 없습니다!".to_string(),
+                _ => "No microphone found!".to_string(),
+            }
+        }
+        "NO_OUTPUT_DEVICE" => {
+            match lang {
+                "vi" => "Không tìm thấy thiết bị phát âm thanh để ghi!".to_string(),
+                "ko" => "녹음할 출력 장치를 찾을 수 없습니다!".to_string(),
+                _ => "No output device found to record from!".to_string(),
+            }
+        }
+        "AUDIO_CONFIG_FAILED" => {
+            match lang {
+                "vi" => "Không đọc được cấu hình thiết bị âm thanh!".to_string(),
+                "ko" => "오디오 장치 설정을 읽을 수 없습니다!".to_string(),
+                _ => "Couldn't read the audio device settings!".to_string(),
+            }
+        }
+        "AUDIO_STREAM_FAILED" => {
+            match lang {
+                "vi" => "Không mở được luồng ghi âm (thiết bị đang bận hoặc bị ngắt?)".to_string(),
+                "ko" => "녹음 스트림을 열 수 없습니다 (장치가 사용 중이거나 연결이 끊겼나요?)".to_string(),
+                _ => "Couldn't start recording (device busy or disconnected?)".to_string(),
+            }
+        }
+        "UNSUPPORTED_SAMPLE_FORMAT" => {
+            match lang {
+                "vi" => "Thiết bị dùng định dạng âm thanh không hỗ trợ!".to_string(),
+                "ko" => "지원되지 않는 오디오 형식의 장치입니다!".to_string(),
+                _ => "The device uses an unsupported audio format!".to_string(),
+            }
+        }
+        "AUDIO_ENCODE_FAILED" => {
+            match lang {
+                "vi" => "Không mã hóa được bản ghi âm!".to_string(),
+                "ko" => "녹음을 인코딩할 수 없습니다!".to_string(),
+                _ => "Couldn't encode the recording!".to_string(),
+            }
+        }
+        "AUDIO_DECODE_FAILED" => {
+            match lang {
+                "vi" => "Không đọc được tệp âm thanh!".to_string(),
+                "ko" => "오디오 파일을 읽을 수 없습니다!".to_string(),
+                _ => "Couldn't read the audio file!".to_string(),
+            }
+        }
+        "EMPTY_RECORDING" => {
+            match lang {
+                "vi" => "Không có âm thanh nào để xử lý!".to_string(),
+                "ko" => "처리할 오디오가 없습니다!".to_string(),
+                _ => "There is no audio to process!".to_string(),
+            }
+        }
+        "LOCAL_OCR_UNAVAILABLE" => {
+            match lang {
+                "vi" => "Không dùng được OCR ngoại tuyến! Hãy cài gói ngôn ngữ OCR trong Cài đặt Windows.".to_string(),
+                "ko" => "오프라인 OCR을 사용할 수 없습니다! Windows 설정에서 OCR 언어 팩을 설치하세요.".to_string(),
+                _ => "Offline OCR is unavailable! Install the OCR language pack in Windows Settings.".to_string(),
+            }
+        }
+        "NO_TEXT_FOUND" => {
+            match lang {
+                "vi" => "Không tìm thấy chữ nào trong ảnh!".to_string(),
+                "ko" => "이미지에서 텍스트를 찾을 수 없습니다!".to_string(),
+                _ => "No text was found in the image!".to_string(),
+            }
+        }
+        "NO_TEXT_TO_SPEAK" => {
+            match lang {
+                "vi" => "Không có nội dung để đọc!".to_string(),
+                "ko" => "읽을 텍스트가 없습니다!".to_string(),
+                _ => "There is no text to read aloud!".to_string(),
+            }
+        }
+        "HISTORY_MEDIA_MISSING" => {
+            match lang {
+                "vi" => "Không tìm thấy ảnh/âm thanh của mục lịch sử này!".to_string(),
+                "ko" => "이 기록 항목의 미디어 파일을 찾을 수 없습니다!".to_string(),
+                _ => "The media of this history entry is missing!".to_string(),
+            }
+        }
+        "LOCAL_TTS_NOT_AVAILABLE" => {
+            match lang {
+                "vi" => "Chưa có bộ đọc giọng nói cục bộ!".to_string(),
+                "ko" => "로컬 음성 엔진을 사용할 수 없습니다!".to_string(),
+                _ => "No local speech engine is available!".to_string(),
+            }
+        }
         _ => {
             match lang {
                 "vi" => format!("Lỗi: {}", error),
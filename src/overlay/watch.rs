@@ -0,0 +1,274 @@
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::{GetMonitorInfoW, MonitorFromRect, MONITORINFO, MONITOR_DEFAULTTONEAREST};
+use windows::Win32::UI::WindowsAndMessaging::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use image::{imageops::FilterType, GrayImage, ImageBuffer, Rgba};
+
+use crate::api::{translate_image_streaming, translate_text_streaming};
+use crate::api::ocr::recognize_image_streaming;
+use crate::api::schema::OutputSchema;
+use crate::config::{Config, Preset};
+use crate::imaging::preprocess::{prepare as prepare_image, PrepOptions};
+use super::process::{spawn_result_window, open_live_translation_window};
+use super::region::capture_screen_rect;
+use super::result::{update_window_text, set_window_history_id, WindowType, RefineContext};
+use super::utils::get_error_message;
+
+// --- WATCH MODE ---
+// Live translation of game dialogue or video subtitles: the region is sampled
+// at the preset's interval and re-sent only once its content has changed and
+// settled. One result window (plus one for the retranslation) stays open and
+// is updated in place, and the session is kept as a single history entry.
+// The preset's hotkey (or closing the window) stops it.
+
+const DIFF_SAMPLE_WIDTH: u32 = 160; // Frames are compared at this width
+const PIXEL_DELTA: u8 = 24; // Luma difference that counts a pixel as changed
+const MIN_INTERVAL_MS: u32 = 200;
+const RESULT_GAP: i32 = 8;
+
+lazy_static::lazy_static! {
+    // Stop flags of running watches, by preset index
+    static ref WATCHES: Mutex<HashMap<usize, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
+}
+
+// Returns true when a watch was running for the preset
+pub fn stop_watch(preset_idx: usize) -> bool {
+    match WATCHES.lock().unwrap().remove(&preset_idx) {
+        Some(stop) => {
+            stop.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+// Blocks until the watch stops, so call it from a worker thread
+pub fn start_watch(rect: RECT, config: Config, preset: Preset, preset_idx: usize) {
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(old) = WATCHES.lock().unwrap().insert(preset_idx, stop.clone()) {
+        old.store(true, Ordering::SeqCst);
+    }
+
+    run_watch(rect, &config, &preset, &stop);
+
+    let mut watches = WATCHES.lock().unwrap();
+    if watches.get(&preset_idx).is_some_and(|s| Arc::ptr_eq(s, &stop)) {
+        watches.remove(&preset_idx);
+    }
+}
+
+// Everything a request needs that stays the same for the whole watch
+struct WatchRequest {
+    model_name: String,
+    provider: String,
+    prompt: String,
+    schema: Option<OutputSchema>,
+    prep_options: PrepOptions,
+}
+
+// Result windows and the history entry of one watch session
+struct WatchSession {
+    hwnd: HWND,
+    translation_hwnd: Option<HWND>,
+    history_id: Option<i64>,
+    text: String,
+    translation: String,
+}
+
+fn work_area(rect: RECT) -> RECT {
+    unsafe {
+        let hmonitor = MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST);
+        let mut mi = MONITORINFO { cbSize: std::mem::size_of::<MONITORINFO>() as u32, ..Default::default() };
+        if GetMonitorInfoW(hmonitor, &mut mi).as_bool() {
+            mi.rcWork
+        } else {
+            rect
+        }
+    }
+}
+
+// `count` result windows stacked just below the region so they never cover the
+// watched text; above it when that would leave the work area, and over the
+// region's lower part only when neither side has room
+fn result_rects(region: RECT, count: i32) -> Vec<RECT> {
+    let work = work_area(region);
+    let height = (region.bottom - region.top).clamp(40, 160);
+    let total = count * (height + RESULT_GAP);
+
+    let top = if region.bottom + total <= work.bottom {
+        region.bottom + RESULT_GAP
+    } else if region.top - total >= work.top {
+        region.top - total
+    } else {
+        work.bottom - total
+    }.max(work.top);
+    let width = (region.right - region.left).min(work.right - work.left);
+    let left = region.left.clamp(work.left, work.right - width);
+
+    (0..count).map(|i| {
+        let top = top + i * (height + RESULT_GAP);
+        RECT { left, top, right: left + width, bottom: top + height }
+    }).collect()
+}
+
+fn run_watch(rect: RECT, config: &Config, preset: &Preset, stop: &AtomicBool) {
+    let model_config = match crate::model_config::get_model_by_id(&preset.model) {
+        Some(m) => m,
+        None => return,
+    };
+    let request = WatchRequest {
+        model_name: model_config.full_name,
+        provider: model_config.provider,
+        prompt: preset.expanded_prompt(),
+        schema: OutputSchema::from_preset(preset),
+        prep_options: PrepOptions::from_preset(preset),
+    };
+    let interval = std::time::Duration::from_millis(preset.watch_interval_ms.max(MIN_INTERVAL_MS) as u64);
+    let threshold = preset.watch_threshold.max(0.0) / 100.0;
+
+    let rects = result_rects(rect, if preset.retranslate { 2 } else { 1 });
+    let hwnd = spawn_result_window(rects[0], WindowType::Primary, RefineContext::None, preset.model.clone(), request.provider.clone(), false, !preset.hide_overlay);
+    if hwnd.0 == 0 {
+        return;
+    }
+    let translation_hwnd = rects.get(1).map(|r| open_live_translation_window(preset, *r, hwnd));
+    unsafe {
+        // Keeps the windows out of our own samples if they are dragged over the region (Windows 10 2004+)
+        for window in std::iter::once(hwnd).chain(translation_hwnd) {
+            let _ = SetWindowDisplayAffinity(window, WDA_EXCLUDEFROMCAPTURE);
+        }
+    }
+    let mut session = WatchSession { hwnd, translation_hwnd, history_id: None, text: String::new(), translation: String::new() };
+
+    let mut last_sent: Option<GrayImage> = None;
+    let mut previous: Option<GrayImage> = None;
+
+    while !stop.load(Ordering::SeqCst) && unsafe { IsWindow(hwnd).as_bool() } {
+        if let Ok(frame) = capture_screen_rect(rect) {
+            let sample = diff_sample(&frame);
+            // Changed since the last request, and not mid-animation (same as the previous sample)
+            let changed = last_sent.as_ref().is_none_or(|sent| changed_fraction(sent, &sample) > threshold);
+            let settled = previous.as_ref().is_some_and(|prev| changed_fraction(prev, &sample) <= threshold);
+            previous = Some(sample.clone());
+
+            if changed && settled {
+                last_sent = Some(sample);
+                send_frame(frame, &mut session, config, preset, &request);
+            }
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+fn send_frame(frame: ImageBuffer<Rgba<u8>, Vec<u8>>, session: &mut WatchSession, config: &Config, preset: &Preset, request: &WatchRequest) {
+    let hwnd = session.hwnd;
+    let mut png_data = Vec::new();
+    let _ = frame.write_to(&mut std::io::Cursor::new(&mut png_data), image::ImageFormat::Png);
+    let prepared = prepare_image(&frame, &png_data, &request.prep_options);
+
+    let result = if request.provider == "local" {
+        recognize_image_streaming(&request.model_name, &prepared, &preset.ocr_language, |_| {})
+    } else {
+        translate_image_streaming(
+            &config.api_key,
+            &config.gemini_api_key,
+            request.prompt.clone(),
+            request.model_name.clone(),
+            request.provider.clone(),
+            &prepared,
+            false,
+            request.schema.as_ref(),
+            |_| {}
+        )
+    };
+
+    match result {
+        Ok(text) if !text.trim().is_empty() => {
+            update_window_text(hwnd, &text);
+            let translation = session.translation_hwnd.and_then(|translation_hwnd| retranslate(&text, translation_hwnd, config, preset));
+            record_history(session, frame, &text, translation.as_deref(), preset);
+        }
+        Ok(_) => {}
+        // The text just disappeared (e.g. between two subtitles)
+        Err(e) if e.to_string() == "NO_TEXT_FOUND" => {}
+        Err(e) => update_window_text(hwnd, &get_error_message(&e.to_string(), &config.ui_language)),
+    }
+}
+
+// Retranslation of one result into the secondary window, None on failure
+fn retranslate(text: &str, hwnd: HWND, config: &Config, preset: &Preset) -> Option<String> {
+    let (model_name, provider) = match crate::model_config::get_model_by_id(&preset.retranslate_model) {
+        Some(m) => (m.full_name, m.provider),
+        None => ("openai/gpt-oss-20b".to_string(), "groq".to_string()),
+    };
+    let result = translate_text_streaming(
+        &config.api_key, &config.gemini_api_key, text.to_string(), preset.retranslate_to.clone(),
+        model_name, provider, false, false, |_| {}
+    );
+    match result {
+        Ok(translation) => {
+            update_window_text(hwnd, &translation);
+            Some(translation)
+        }
+        Err(e) => {
+            update_window_text(hwnd, &get_error_message(&e.to_string(), &config.ui_language));
+            None
+        }
+    }
+}
+
+// The first result creates the session's history entry (with its frame), later
+// ones are appended to its text
+fn record_history(session: &mut WatchSession, frame: ImageBuffer<Rgba<u8>, Vec<u8>>, text: &str, translation: Option<&str>, preset: &Preset) {
+    let app = match crate::APP.lock() {
+        Ok(app) => app,
+        Err(_) => return,
+    };
+    if !session.text.is_empty() {
+        session.text.push_str("\n\n");
+    }
+    session.text.push_str(text.trim());
+
+    let id = match session.history_id {
+        Some(id) => {
+            app.history.update_text(id, session.text.clone());
+            id
+        }
+        None => {
+            let id = app.history.save_image(frame, session.text.clone(), None);
+            set_window_history_id(session.hwnd, id);
+            session.history_id = Some(id);
+            id
+        }
+    };
+
+    if let Some(translation) = translation {
+        if !session.translation.is_empty() {
+            session.translation.push_str("\n\n");
+        }
+        session.translation.push_str(translation.trim());
+        app.history.set_translation(id, session.translation.clone(), preset.retranslate_to.clone());
+    }
+}
+
+// Small grayscale copy used for frame comparison
+fn diff_sample(frame: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> GrayImage {
+    let gray = image::imageops::grayscale(frame);
+    if gray.width() <= DIFF_SAMPLE_WIDTH {
+        return gray;
+    }
+    let height = ((gray.height() as f32 * DIFF_SAMPLE_WIDTH as f32 / gray.width() as f32) as u32).max(1);
+    image::imageops::resize(&gray, DIFF_SAMPLE_WIDTH, height, FilterType::Triangle)
+}
+
+// Share of pixels (0..1) whose luma moved more than PIXEL_DELTA
+fn changed_fraction(a: &GrayImage, b: &GrayImage) -> f32 {
+    if a.dimensions() != b.dimensions() || a.as_raw().is_empty() {
+        return 1.0;
+    }
+    let changed = a.as_raw().iter().zip(b.as_raw())
+        .filter(|(x, y)| x.abs_diff(**y) > PIXEL_DELTA)
+        .count();
+    changed as f32 / a.as_raw().len() as f32
+}
@@ -0,0 +1,168 @@
+use chrono::Local;
+use serde::{Serialize, Deserialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// --- RESULT CACHE ---
+// API answers keyed by a hash of the captured media, the prompt and the model,
+// so selecting the same dialog box or subtitle again is answered without a
+// request. Kept in result_cache.json beside the history and limited by age and
+// entry count (both from the global settings).
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CacheEntry {
+    key: String,
+    text: String,
+    created: i64, // Unix seconds
+}
+
+lazy_static::lazy_static! {
+    // Loaded on first use
+    static ref ENTRIES: Mutex<Option<Vec<CacheEntry>>> = Mutex::new(None);
+}
+
+fn cache_path() -> PathBuf {
+    dirs::config_dir().unwrap_or_default().join("screen-goated-toolbox").join("result_cache.json")
+}
+
+fn load() -> Vec<CacheEntry> {
+    std::fs::read_to_string(cache_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &[CacheEntry]) {
+    if let Ok(data) = serde_json::to_string(entries) {
+        let _ = std::fs::write(cache_path(), data);
+    }
+}
+
+// FNV-1a over the parts with a 0 byte between them. Unlike DefaultHasher it
+// never changes between Rust versions, so cache keys and the TTS audio file
+// names made from it stay valid after an update.
+pub fn content_hash(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (i, part) in parts.iter().enumerate() {
+        let separator: &[u8] = if i > 0 { &[0] } else { &[] };
+        for byte in separator.iter().chain(part.iter()) {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+// `variant` covers request options that change the answer (preprocessing, schema...)
+pub fn request_key(media: &[u8], prompt: &str, model: &str, variant: &str) -> String {
+    format!("{:016x}", content_hash(&[media, prompt.as_bytes(), model.as_bytes(), variant.as_bytes()]))
+}
+
+// ttl_hours = 0 keeps entries forever
+fn is_expired(entry: &CacheEntry, ttl_hours: u32, now: i64) -> bool {
+    ttl_hours > 0 && now - entry.created > ttl_hours as i64 * 3600
+}
+
+fn find_entry<'a>(entries: &'a [CacheEntry], key: &str, ttl_hours: u32, now: i64) -> Option<&'a CacheEntry> {
+    entries.iter().find(|e| e.key == key).filter(|e| !is_expired(e, ttl_hours, now))
+}
+
+// Newest first: replaces an older answer for the key, drops expired entries and
+// whatever is past `max_entries`. Returns false when nothing was stored.
+fn insert_entry(entries: &mut Vec<CacheEntry>, key: String, text: String, ttl_hours: u32, max_entries: usize, now: i64) -> bool {
+    if max_entries == 0 || text.trim().is_empty() {
+        return false;
+    }
+    entries.retain(|e| e.key != key && !is_expired(e, ttl_hours, now));
+    entries.insert(0, CacheEntry { key, text, created: now });
+    entries.truncate(max_entries);
+    true
+}
+
+pub fn lookup(key: &str, ttl_hours: u32) -> Option<String> {
+    let mut guard = ENTRIES.lock().unwrap();
+    let entries = guard.get_or_insert_with(load);
+    find_entry(entries, key, ttl_hours, Local::now().timestamp()).map(|e| e.text.clone())
+}
+
+pub fn store(key: String, text: String, ttl_hours: u32, max_entries: usize) {
+    let mut guard = ENTRIES.lock().unwrap();
+    let entries = guard.get_or_insert_with(load);
+    if insert_entry(entries, key, text, ttl_hours, max_entries, Local::now().timestamp()) {
+        save(entries);
+    }
+}
+
+pub fn clear() {
+    let mut guard = ENTRIES.lock().unwrap();
+    *guard = Some(Vec::new());
+    let _ = std::fs::remove_file(cache_path());
+}
+
+pub fn entry_count() -> usize {
+    let mut guard = ENTRIES.lock().unwrap();
+    guard.get_or_insert_with(load).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    const HOUR: i64 = 3600;
+
+    fn entry(key: &str, text: &str, created: i64) -> CacheEntry {
+        CacheEntry { key: key.to_string(), text: text.to_string(), created }
+    }
+
+    fn keys(entries: &[CacheEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.key.as_str()).collect()
+    }
+
+    #[test]
+    fn hash_separates_parts_only() {
+        // FNV-1a of "a\0b", the key the TTS cache has always used
+        let mut expected: u64 = 0xcbf29ce484222325;
+        for byte in b"a\0b" {
+            expected ^= *byte as u64;
+            expected = expected.wrapping_mul(0x100000001b3);
+        }
+        assert_eq!(content_hash(&[b"a", b"b"]), expected);
+        assert_ne!(content_hash(&[b"ab", b""]), content_hash(&[b"a", b"b"]));
+        assert_eq!(request_key(b"img", "p", "m", "v").len(), 16);
+    }
+
+    #[test]
+    fn lookup_respects_ttl() {
+        let entries = vec![entry("fresh", "one", NOW - HOUR), entry("old", "two", NOW - 25 * HOUR)];
+        assert_eq!(find_entry(&entries, "fresh", 24, NOW).map(|e| e.text.as_str()), Some("one"));
+        assert!(find_entry(&entries, "old", 24, NOW).is_none());
+        // Exactly at the limit still counts as fresh
+        assert!(find_entry(&[entry("edge", "x", NOW - 24 * HOUR)], "edge", 24, NOW).is_some());
+        // No TTL: never expires
+        assert!(find_entry(&entries, "old", 0, NOW).is_some());
+        assert!(find_entry(&entries, "missing", 0, NOW).is_none());
+    }
+
+    #[test]
+    fn store_replaces_evicts_and_truncates() {
+        let mut entries = vec![entry("a", "1", NOW - HOUR), entry("b", "2", NOW - 30 * HOUR), entry("c", "3", NOW - 2 * HOUR)];
+        assert!(insert_entry(&mut entries, "c".to_string(), "new".to_string(), 24, 10, NOW));
+        // Replaced entry moves to the front, the expired one is gone
+        assert_eq!(keys(&entries), vec!["c", "a"]);
+        assert_eq!((entries[0].text.as_str(), entries[0].created), ("new", NOW));
+
+        for key in ["d", "e", "f"] {
+            insert_entry(&mut entries, key.to_string(), "x".to_string(), 24, 3, NOW);
+        }
+        assert_eq!(keys(&entries), vec!["f", "e", "d"]);
+    }
+
+    #[test]
+    fn store_skips_empty_text_and_disabled_cache() {
+        let mut entries = vec![entry("a", "1", NOW)];
+        assert!(!insert_entry(&mut entries, "b".to_string(), "  \n".to_string(), 24, 10, NOW));
+        assert!(!insert_entry(&mut entries, "c".to_string(), "text".to_string(), 24, 0, NOW));
+        assert_eq!(keys(&entries), vec!["a"]);
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::PathBuf;
+use anyhow::Result;
+use crate::api::TranscriptSegment;
+
+// --- SUBTITLE EXPORT ---
+// SRT / WebVTT files from timestamped transcripts, plus an alignment step that
+// puts a retranslated text back onto the source segment timings.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    // Preset value: "srt", "vtt", anything else = no export
+    pub fn from_preset(value: &str) -> Option<Self> {
+        match value {
+            "srt" => Some(SubtitleFormat::Srt),
+            "vtt" => Some(SubtitleFormat::Vtt),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+// 01:02:03,456 (SRT) / 01:02:03.456 (VTT)
+fn cue_time(secs: f64, separator: char) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!("{:02}:{:02}:{:02}{}{:03}", ms / 3_600_000, (ms / 60_000) % 60, (ms / 1000) % 60, separator, ms % 1000)
+}
+
+pub fn to_srt(segments: &[TranscriptSegment]) -> String {
+    let mut out = String::new();
+    for (i, seg) in segments.iter().enumerate() {
+        let text = if seg.speaker.is_empty() { seg.text.trim().to_string() } else { format!("{}: {}", seg.speaker, seg.text.trim()) };
+        out.push_str(&format!("{}\n{} --> {}\n{}\n\n", i + 1, cue_time(seg.start, ','), cue_time(seg.end, ','), text));
+    }
+    out
+}
+
+pub fn to_vtt(segments: &[TranscriptSegment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for seg in segments {
+        // Speakers become WebVTT voice tags
+        let text = if seg.speaker.is_empty() { seg.text.trim().to_string() } else { format!("<v {}>{}", seg.speaker, seg.text.trim()) };
+        out.push_str(&format!("{} --> {}\n{}\n\n", cue_time(seg.start, '.'), cue_time(seg.end, '.'), text));
+    }
+    out
+}
+
+pub fn render(segments: &[TranscriptSegment], format: SubtitleFormat) -> String {
+    match format {
+        SubtitleFormat::Srt => to_srt(segments),
+        SubtitleFormat::Vtt => to_vtt(segments),
+    }
+}
+
+// Drops a leading "[mm:ss]" marker (timestamped result windows are retranslated line by line)
+pub fn strip_timestamp(line: &str) -> &str {
+    let line = line.trim();
+    if line.starts_with('[') {
+        if let Some(end) = line.find(']') {
+            if line[1..end].chars().all(|c| c.is_ascii_digit() || c == ':') {
+                return line[end + 1..].trim();
+            }
+        }
+    }
+    line
+}
+
+// Drops the (possibly translated) "Speaker:" label of a diarized line
+fn strip_speaker<'a>(line: &'a str, speaker: &str) -> &'a str {
+    if speaker.is_empty() {
+        return line;
+    }
+    match line.find(':') {
+        Some(colon) if line[..colon].chars().count() <= speaker.chars().count() + 12 => line[colon + 1..].trim(),
+        _ => line,
+    }
+}
+
+// Maps a translated text onto the source timings. If the translation kept one line
+// per segment the lines map 1:1, otherwise its words are spread over the segments
+// in proportion to the length of the source text.
+pub fn align_translation(segments: &[TranscriptSegment], translated: &str) -> Vec<TranscriptSegment> {
+    let lines: Vec<&str> = translated.lines().map(strip_timestamp).filter(|l| !l.is_empty()).collect();
+    if lines.len() == segments.len() {
+        return segments.iter().zip(lines)
+            .map(|(seg, line)| TranscriptSegment { text: strip_speaker(line, &seg.speaker).to_string(), ..seg.clone() })
+            .collect();
+    }
+
+    let words: Vec<&str> = lines.iter().flat_map(|l| l.split_whitespace()).collect();
+    let total_chars: usize = segments.iter().map(|s| s.text.chars().count().max(1)).sum();
+    let mut aligned = Vec::with_capacity(segments.len());
+    let mut used_chars = 0;
+    let mut next_word = 0;
+
+    for seg in segments {
+        used_chars += seg.text.chars().count().max(1);
+        let until = (words.len() * used_chars + total_chars / 2) / total_chars.max(1);
+        let until = until.clamp(next_word, words.len());
+        aligned.push(TranscriptSegment { text: words[next_word..until].join(" "), ..seg.clone() });
+        next_word = until;
+    }
+    aligned.retain(|s| !s.text.is_empty());
+    aligned
+}
+
+// Subtitles go to the Downloads folder (history media folder as fallback)
+pub fn export_dir() -> PathBuf {
+    dirs::download_dir().unwrap_or_else(|| crate::history::get_media_path(""))
+}
+
+// Writes `<stem>[.<suffix>].<ext>` and returns its path
+pub fn write_subtitle_file(segments: &[TranscriptSegment], format: SubtitleFormat, stem: &str, suffix: Option<&str>) -> Result<PathBuf> {
+    let name = match suffix {
+        Some(suffix) => format!("{}.{}.{}", stem, suffix, format.extension()),
+        None => format!("{}.{}", stem, format.extension()),
+    };
+    let dir = export_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(name);
+    std::fs::write(&path, render(segments, format))?;
+    Ok(path)
+}
+
+// Original track plus, when a retranslation exists, the aligned translated track
+pub fn export_tracks(segments: &[TranscriptSegment], translation: &str, translation_lang: &str, format: SubtitleFormat, stem: &str) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![write_subtitle_file(segments, format, stem, None)?];
+    if !translation.trim().is_empty() {
+        let aligned = align_translation(segments, translation);
+        let suffix = if translation_lang.is_empty() { "translated" } else { translation_lang };
+        paths.push(write_subtitle_file(&aligned, format, stem, Some(suffix))?);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f64, end: f64, text: &str, speaker: &str) -> TranscriptSegment {
+        TranscriptSegment { start, end, text: text.to_string(), speaker: speaker.to_string() }
+    }
+
+    #[test]
+    fn cue_times() {
+        assert_eq!(cue_time(0.0, ','), "00:00:00,000");
+        assert_eq!(cue_time(3725.4567, ','), "01:02:05,457");
+        assert_eq!(cue_time(12.0004, '.'), "00:00:12.000");
+        // Rounding carries into the next second/minute
+        assert_eq!(cue_time(59.9996, '.'), "00:01:00.000");
+        assert_eq!(cue_time(-1.0, ','), "00:00:00,000");
+    }
+
+    #[test]
+    fn srt_and_vtt_output() {
+        let segments = vec![seg(0.5, 2.25, " Hello there ", ""), seg(3661.0, 3662.5, "Hi", "Speaker 2")];
+        assert_eq!(
+            to_srt(&segments),
+            "1\n00:00:00,500 --> 00:00:02,250\nHello there\n\n2\n01:01:01,000 --> 01:01:02,500\nSpeaker 2: Hi\n\n"
+        );
+        assert_eq!(
+            to_vtt(&segments),
+            "WEBVTT\n\n00:00:00.500 --> 00:00:02.250\nHello there\n\n01:01:01.000 --> 01:01:02.500\n<v Speaker 2>Hi\n\n"
+        );
+    }
+
+    #[test]
+    fn align_one_line_per_segment() {
+        let segments = vec![seg(0.0, 2.0, "Good morning", "Speaker 1"), seg(2.0, 4.0, "How are you", "Speaker 2")];
+        let aligned = align_translation(&segments, "[00:00] Hablante 1: Buenos días\n\n[00:02] Hablante 2: ¿Cómo estás?");
+        assert_eq!(aligned.len(), 2);
+        assert_eq!(aligned[0].text, "Buenos días");
+        assert_eq!(aligned[1].text, "¿Cómo estás?");
+        assert_eq!((aligned[1].start, aligned[1].end), (2.0, 4.0));
+        assert_eq!(aligned[1].speaker, "Speaker 2");
+    }
+
+    #[test]
+    fn align_spreads_words_when_line_counts_differ() {
+        let segments = vec![
+            seg(0.0, 1.0, "one two", ""),
+            seg(1.0, 3.0, "three four five six", ""),
+            seg(3.0, 4.0, "seven eight", ""),
+        ];
+        let aligned = align_translation(&segments, "un deux trois\nquatre cinq six sept huit");
+        let texts: Vec<&str> = aligned.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["un deux", "trois quatre cinq six", "sept huit"]);
+
+        // Fewer words than segments: empty cues are dropped, the order is kept
+        let aligned = align_translation(&segments, "bonjour");
+        assert_eq!(aligned.len(), 1);
+        assert_eq!(aligned[0].text, "bonjour");
+    }
+}